@@ -0,0 +1,139 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// The allocations of a single run of a closure.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+    /// Allocations and reallocations.
+    pub count: usize,
+    /// Bytes requested by all allocations, a reallocation counts its new size.
+    pub bytes: usize,
+    /// The most bytes that were allocated at the same time, on top of what
+    /// was allocated before the run.
+    pub peak: usize,
+}
+
+/// Wraps the system allocator and counts the allocations of every thread.
+///
+/// It is the global allocator if the `count-allocations` feature is enabled.
+pub struct CountingAllocator;
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Clone, Copy)]
+struct Counters {
+    count: usize,
+    bytes: usize,
+    /// Memory freed on this thread that another thread allocated makes it negative.
+    live: isize,
+    peak: isize,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters { count: 0, bytes: 0, live: 0, peak: 0 })
+    };
+}
+
+fn update(f: impl FnOnce(&mut Counters)) {
+    // the thread local is gone while the thread shuts down
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        f(&mut c);
+        c.peak = c.peak.max(c.live);
+        counters.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        update(|c| {
+            c.count += 1;
+            c.bytes += layout.size();
+            c.live += layout.size() as isize;
+        });
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        update(|c| {
+            c.count += 1;
+            c.bytes += layout.size();
+            c.live += layout.size() as isize;
+        });
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        update(|c| c.live -= layout.size() as isize);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        update(|c| {
+            c.count += 1;
+            c.bytes += new_size;
+            c.live += new_size as isize - layout.size() as isize;
+        });
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Whether allocations are counted, i.e. the `count-allocations` feature is
+/// enabled.
+pub const fn enabled() -> bool {
+    cfg!(feature = "count-allocations")
+}
+
+/// Runs `f` and counts the allocations it does on the current thread, `None`
+/// if allocations are not counted.
+pub fn count<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    if !enabled() {
+        return (f(), None);
+    }
+
+    let before = COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        c.peak = c.live;
+        counters.set(c);
+        c
+    });
+    let result = f();
+    let after = COUNTERS.with(Cell::get);
+    let allocations = Allocations {
+        count: after.count - before.count,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.live).max(0) as usize,
+    };
+    (result, Some(allocations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count() {
+        let (v, allocations) = count(|| {
+            let mut v = Vec::<u64>::with_capacity(4);
+            v.extend([1, 2, 3, 4, 5]);
+            drop(vec![0_u8; 1000]);
+            v
+        });
+        assert_eq!(v.len(), 5);
+        if !enabled() {
+            assert_eq!(allocations, None);
+            return;
+        }
+        let allocations = allocations.unwrap();
+        // the vector grows once, the second one is freed again
+        assert_eq!(allocations.count, 3);
+        assert_eq!(allocations.bytes, 32 + 64 + 1000);
+        assert_eq!(allocations.peak, 64 + 1000);
+
+        let (_, allocations) = count(|| 1 + 1);
+        assert_eq!(allocations, Some(Allocations::default()));
+    }
+}
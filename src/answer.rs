@@ -0,0 +1,251 @@
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::ocr;
+
+/// The answer to a part of a puzzle.
+///
+/// Answers are serialized as integers or as strings, so that a text answer
+/// of digits stays text, and printed as their [`Display`] representation,
+/// which can be read back with [`FromStr`]. A [`Answer::Grid`] is stored as
+/// its decoded text, which is why grids compare equal to text answers with
+/// the same letters. Grids that don't decode cleanly only compare equal to
+/// grids with the same pixels.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+    /// A rendered grid of `#` and `.`, like the CRT screen of day 10.
+    Grid {
+        rendered: String,
+        decoded: String,
+    },
+}
+
+impl Answer {
+    /// Builds a grid answer, decoding the letters it shows.
+    pub fn grid(rendered: impl Into<String>) -> Self {
+        let rendered = rendered.into();
+        let decoded = ocr::decode(&rendered);
+        Self::Grid { rendered, decoded }
+    }
+
+    /// Whether every letter of a grid could be read, always true for the
+    /// other answers.
+    pub fn is_decoded(&self) -> bool {
+        match self {
+            Answer::Grid { decoded, .. } => !decoded.is_empty() && !decoded.contains('?'),
+            _ => true,
+        }
+    }
+
+    /// The text answer, or the decoded text of a grid.
+    fn text(&self) -> Option<&str> {
+        match self {
+            Answer::Integer(_) => None,
+            Answer::Text(text) | Answer::Grid { decoded: text, .. } => Some(text),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Integer(a), Answer::Integer(b)) => a == b,
+            (Answer::Grid { rendered: a, .. }, Answer::Grid { rendered: b, .. })
+                if !self.is_decoded() || !other.is_decoded() =>
+            {
+                a == b
+            }
+            _ if !self.is_decoded() || !other.is_decoded() => false,
+            _ => matches!((self.text(), other.text()), (Some(a), Some(b)) if a == b),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Grid { decoded, .. } => write!(f, "{decoded}"),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(if let Ok(n) = s.parse() {
+            Answer::Integer(n)
+        } else if s.contains('\n') {
+            Answer::grid(s)
+        } else {
+            Answer::Text(s.to_string())
+        })
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Integer(n)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Answer::Integer(n as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Integer(i64::try_from(n).expect("answer does not fit into an i64"))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(n) => serializer.serialize_i64(*n),
+            Answer::Text(text) | Answer::Grid { decoded: text, .. } => {
+                serializer.serialize_str(text)
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AnswerVisitor;
+
+        impl Visitor<'_> for AnswerVisitor {
+            type Value = Answer;
+
+            fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+                write!(f, "an integer or a string")
+            }
+
+            fn visit_i64<E: Error>(self, n: i64) -> Result<Answer, E> {
+                Ok(Answer::Integer(n))
+            }
+
+            fn visit_u64<E: Error>(self, n: u64) -> Result<Answer, E> {
+                i64::try_from(n)
+                    .map(Answer::Integer)
+                    .map_err(|_| E::custom(format!("answer {n} does not fit into an i64")))
+            }
+
+            fn visit_str<E: Error>(self, s: &str) -> Result<Answer, E> {
+                Ok(Answer::from(s))
+            }
+        }
+
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN: &str = "\
+####..##....##.###...##...##..####.#..#.
+#....#..#....#.#..#.#..#.#..#.#....#.#..
+###..#.......#.###..#....#....###..##...
+#....#.##....#.#..#.#.##.#....#....#.#..
+#....#..#.#..#.#..#.#..#.#..#.#....#.#..
+####..###..##..###...###..##..#....#..#.
+";
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("68442".parse(), Ok(Answer::Integer(68442)));
+        assert_eq!("-3".parse(), Ok(Answer::Integer(-3)));
+        assert_eq!(" CMZ\n".parse(), Ok(Answer::Text("CMZ".to_string())));
+        assert_eq!(SCREEN.parse(), Ok(Answer::Text("EGJBGCFK".to_string())));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for answer in [
+            Answer::Integer(35270398814),
+            Answer::from("SBPQRSCDF"),
+            Answer::grid(SCREEN),
+        ] {
+            assert_eq!(answer.to_string().parse(), Ok(answer));
+        }
+    }
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(Answer::grid(SCREEN), Answer::from("EGJBGCFK"));
+        assert!(Answer::grid(SCREEN).is_decoded());
+        assert!(Answer::from("A?").is_decoded());
+
+        // the last letter is unknown in both, but with other pixels
+        let flip = |pixel: usize| {
+            let mut screen = SCREEN.as_bytes().to_vec();
+            screen[pixel] = if screen[pixel] == b'#' { b'.' } else { b'#' };
+            String::from_utf8(screen).unwrap()
+        };
+        let (unknown, other) = (flip(36), flip(37));
+        assert_eq!(Answer::grid(&unknown).to_string(), "EGJBGCF?");
+        assert_eq!(Answer::grid(&other).to_string(), "EGJBGCF?");
+        assert!(!Answer::grid(&unknown).is_decoded());
+        assert_ne!(Answer::grid(&unknown), Answer::grid(&other));
+        assert_eq!(Answer::grid(&unknown), Answer::grid(&unknown));
+        assert_ne!(Answer::grid(&unknown), Answer::from("EGJBGCF?"));
+        assert_ne!(Answer::from("EGJBGCF?"), Answer::grid(&unknown));
+        assert!(!Answer::grid("#\n").is_decoded());
+        assert_ne!(Answer::from("12"), Answer::Integer(12));
+        assert_ne!(Answer::from(12_usize), Answer::Integer(13));
+    }
+
+    #[test]
+    fn test_serde() {
+        assert_eq!(serde_json::to_string(&Answer::Integer(4)).unwrap(), "4");
+        assert_eq!(
+            serde_json::to_string(&Answer::grid(SCREEN)).unwrap(),
+            r#""EGJBGCFK""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Answer>("24000").unwrap(),
+            Answer::Integer(24000)
+        );
+        assert_eq!(
+            serde_json::from_str::<Answer>(r#""CMZ""#).unwrap(),
+            Answer::from("CMZ")
+        );
+        for answer in [
+            Answer::from("12"),
+            Answer::Integer(12),
+            Answer::from(" 7\n"),
+        ] {
+            let stored = serde_json::to_string(&answer).unwrap();
+            let loaded = serde_json::from_str::<Answer>(&stored).unwrap();
+            assert_eq!(loaded, answer, "{stored}");
+            assert_eq!(loaded.to_string(), answer.to_string());
+        }
+    }
+}
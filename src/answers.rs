@@ -0,0 +1,509 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::runner::DayResult;
+
+/// The name of the answers file inside the inputs directory.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Known answers, keyed by day and the name of the input they belong to.
+/// Answers that were rejected when submitting them are kept as well.
+///
+/// ```toml
+/// [day1.default]
+/// part1 = 24000
+/// part2 = 45000
+///
+/// [day10.default]
+/// part1 = 13140
+/// part2 = "EGJBGCFK"
+///
+/// [day11.default.part1_wrong]
+/// answers = [1000, 20]
+/// too_high = 1000
+/// too_low = 20
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    days: BTreeMap<String, BTreeMap<String, PartAnswers>>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PartAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<Answer>,
+    #[serde(default, skip_serializing_if = "WrongAnswers::is_empty")]
+    part1_wrong: WrongAnswers,
+    #[serde(default, skip_serializing_if = "WrongAnswers::is_empty")]
+    part2_wrong: WrongAnswers,
+}
+
+/// The rejected answers of a part, and the bounds they give the right
+/// answer if the website said they were too high or too low.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct WrongAnswers {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    answers: Vec<Answer>,
+    /// The lowest answer that was too high.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    too_high: Option<i64>,
+    /// The highest answer that was too low.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    too_low: Option<i64>,
+}
+
+impl WrongAnswers {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn contains(&self, answer: &Answer) -> bool {
+        match answer {
+            Answer::Integer(n) => {
+                self.answers.contains(answer)
+                    || self.too_high.is_some_and(|high| *n >= high)
+                    || self.too_low.is_some_and(|low| *n <= low)
+            }
+            _ => self.answers.contains(answer),
+        }
+    }
+
+    fn add(&mut self, answer: Answer, hint: Option<Ordering>) {
+        if let Answer::Integer(n) = answer {
+            match hint {
+                Some(Ordering::Greater) => {
+                    self.too_high = Some(self.too_high.map_or(n, |high| high.min(n)))
+                }
+                Some(Ordering::Less) => {
+                    self.too_low = Some(self.too_low.map_or(n, |low| low.max(n)))
+                }
+                _ => {}
+            }
+        }
+        if !self.answers.contains(&answer) {
+            self.answers.push(answer);
+        }
+    }
+}
+
+impl PartAnswers {
+    fn part(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    fn part_mut(&mut self, part: u8) -> &mut Option<Answer> {
+        match part {
+            1 => &mut self.part1,
+            2 => &mut self.part2,
+            _ => panic!("there is no part {part}"),
+        }
+    }
+
+    fn wrong(&self, part: u8) -> Option<&WrongAnswers> {
+        match part {
+            1 => Some(&self.part1_wrong),
+            2 => Some(&self.part2_wrong),
+            _ => None,
+        }
+    }
+
+    fn wrong_mut(&mut self, part: u8) -> &mut WrongAnswers {
+        match part {
+            1 => &mut self.part1_wrong,
+            2 => &mut self.part2_wrong,
+            _ => panic!("there is no part {part}"),
+        }
+    }
+}
+
+impl Answers {
+    /// Loads the answers file, a missing file has no known answers.
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content).map_err(|source| AnswersError::Parse {
+                path: path.to_path_buf(),
+                source,
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(AnswersError::Io {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        let content = toml::to_string(self).expect("answers are serializable");
+        std::fs::write(path, content).map_err(|source| AnswersError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn get(&self, day: u8, input: &str, part: u8) -> Option<&Answer> {
+        self.days.get(&day_key(day))?.get(input)?.part(part)
+    }
+
+    pub fn set(&mut self, day: u8, input: &str, part: u8, answer: Answer) {
+        *self
+            .days
+            .entry(day_key(day))
+            .or_default()
+            .entry(input.to_string())
+            .or_default()
+            .part_mut(part) = Some(answer);
+    }
+
+    /// Remembers an answer that was rejected, `hint` says whether it was
+    /// too high ([`Ordering::Greater`]) or too low, if the website said so.
+    pub fn reject(
+        &mut self,
+        day: u8,
+        input: &str,
+        part: u8,
+        answer: Answer,
+        hint: Option<Ordering>,
+    ) {
+        self.days
+            .entry(day_key(day))
+            .or_default()
+            .entry(input.to_string())
+            .or_default()
+            .wrong_mut(part)
+            .add(answer, hint);
+    }
+
+    /// Whether an answer was rejected before, or is known to be too high or
+    /// too low because of the answers that were.
+    pub fn is_rejected(&self, day: u8, input: &str, part: u8, answer: &Answer) -> bool {
+        self.days
+            .get(&day_key(day))
+            .and_then(|inputs| inputs.get(input)?.wrong(part))
+            .is_some_and(|wrong| wrong.contains(answer))
+    }
+
+    pub fn check(&self, day: u8, input: &str, part: u8, answer: &Answer) -> Verdict {
+        match self.get(day, input, part) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+
+    /// Sets the verdict of every solved part of the results, where `inputs`
+    /// has the name of the input of every day. Variants are checked against
+    /// the answer of their part.
+    ///
+    /// With `record`, the answers of parts without a known answer are
+    /// remembered, except grids that cannot be decoded: every other
+    /// unreadable grid would pass against them.
+    pub fn verify(
+        &mut self,
+        results: &mut [DayResult],
+        inputs: &[String],
+        record: bool,
+    ) -> Verification {
+        let mut verification = Verification::default();
+        for (day, input) in results.iter_mut().zip(inputs) {
+            for part in &mut day.parts {
+                let Some(answer) = &part.answer else {
+                    verification.errors += 1;
+                    continue;
+                };
+                let verdict = self.check(day.day, input, part.part.part, answer);
+                match verdict {
+                    Verdict::Pass => verification.passed += 1,
+                    Verdict::Fail { .. } => verification.failed += 1,
+                    Verdict::Unknown => verification.unknown += 1,
+                }
+                if verdict == Verdict::Unknown && record {
+                    if !answer.is_decoded() {
+                        verification
+                            .undecodable
+                            .push(format!("day{}::{}", day.day, part.part.name));
+                    } else {
+                        self.set(day.day, input, part.part.part, answer.clone());
+                        verification.recorded += 1;
+                    }
+                }
+                part.verdict = Some(verdict);
+            }
+        }
+        verification
+    }
+}
+
+fn day_key(day: u8) -> String {
+    format!("day{day}")
+}
+
+/// The result of comparing an answer with the known answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: Answer },
+    Unknown,
+}
+
+impl Verdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "fail",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {expected})"),
+            _ => write!(f, "{}", self.as_str().to_uppercase()),
+        }
+    }
+}
+
+/// The number of parts of every verdict after [`Answers::verify`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub passed: usize,
+    pub failed: usize,
+    pub unknown: usize,
+    /// Parts that could not be solved.
+    pub errors: usize,
+    /// Answers that were not known and are now.
+    pub recorded: usize,
+    /// Parts with a grid that cannot be decoded, which were not recorded.
+    pub undecodable: Vec<String>,
+}
+
+impl Verification {
+    /// Whether every part was solved and no answer is wrong.
+    pub fn is_success(&self) -> bool {
+        self.failed == 0 && self.errors == 0
+    }
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} unknown, {} errors",
+            self.passed, self.failed, self.unknown, self.errors
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::Io { path, source } => {
+                write!(f, "cannot access answers file {}: {source}", path.display())
+            }
+            AnswersError::Parse { path, source } => {
+                write!(f, "invalid answers file {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AnswersError::Io { source, .. } => Some(source),
+            AnswersError::Parse { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use crate::runner::{failed_day, run_day, Timer};
+
+    const ANSWERS: &str = r#"
+[day1.default]
+part1 = 24000
+part2 = 45000
+
+[day10.default]
+part2 = "EGJBGCFK"
+
+[day10.alice]
+part1 = 13140
+"#;
+
+    #[test]
+    fn test_check() {
+        let answers: Answers = toml::from_str(ANSWERS).unwrap();
+        assert_eq!(
+            answers.check(1, "default", 1, &Answer::Integer(24000)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(1, "default", 2, &Answer::Integer(1)),
+            Verdict::Fail {
+                expected: Answer::Integer(45000)
+            }
+        );
+        assert_eq!(
+            answers.check(10, "default", 1, &Answer::Integer(13140)),
+            Verdict::Unknown
+        );
+        assert_eq!(
+            answers.check(10, "alice", 1, &Answer::Integer(13140)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(10, "default", 2, &Answer::from("EGJBGCFK")),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(2, "default", 1, &Answer::Integer(15)),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.set(10, "default", 2, Answer::from("EGJBGCFK"));
+        answers.set(1, "default", 1, Answer::Integer(24000));
+        answers.set(1, "default", 2, Answer::Integer(45000));
+        answers.set(10, "alice", 1, Answer::Integer(13140));
+        let stored = toml::to_string(&answers).unwrap();
+        assert_eq!(toml::from_str::<Answers>(&stored).unwrap(), answers);
+
+        let mut digits = answers.clone();
+        digits.set(5, "default", 1, Answer::from("12"));
+        let stored = toml::to_string(&digits).unwrap();
+        assert!(
+            stored.contains("[day5.default]\npart1 = \"12\"\n"),
+            "{stored}"
+        );
+        let loaded = toml::from_str::<Answers>(&stored).unwrap();
+        assert_eq!(loaded.get(5, "default", 1), Some(&Answer::from("12")));
+        assert_eq!(
+            loaded.check(5, "default", 1, &Answer::Integer(12)),
+            Verdict::Fail {
+                expected: Answer::from("12")
+            }
+        );
+        assert_eq!(
+            toml::from_str::<Answers>(ANSWERS).unwrap(),
+            answers,
+            "{stored}"
+        );
+    }
+
+    #[test]
+    fn test_reject() {
+        let mut answers = Answers::default();
+        answers.reject(
+            11,
+            "default",
+            1,
+            Answer::Integer(1000),
+            Some(Ordering::Greater),
+        );
+        answers.reject(
+            11,
+            "default",
+            1,
+            Answer::Integer(2000),
+            Some(Ordering::Greater),
+        );
+        answers.reject(11, "default", 1, Answer::Integer(20), Some(Ordering::Less));
+        answers.reject(11, "default", 1, Answer::Integer(500), None);
+        answers.reject(10, "default", 2, Answer::from("ABCDEFGH"), None);
+
+        let rejected = |day, part, answer| answers.is_rejected(day, "default", part, &answer);
+        assert!(rejected(11, 1, Answer::Integer(500)));
+        assert!(rejected(11, 1, Answer::Integer(1000)));
+        assert!(rejected(11, 1, Answer::Integer(1500)));
+        assert!(rejected(11, 1, Answer::Integer(-3)));
+        assert!(!rejected(11, 1, Answer::Integer(999)));
+        assert!(!rejected(11, 2, Answer::Integer(500)));
+        assert!(rejected(10, 2, Answer::from("ABCDEFGH")));
+        assert!(!rejected(10, 2, Answer::from("EGJBGCFK")));
+        assert!(!answers.is_rejected(11, "alice", 1, &Answer::Integer(500)));
+
+        let stored = toml::to_string(&answers).unwrap();
+        assert!(stored.contains("[day11.default.part1_wrong]\nanswers = [1000, 2000, 20, 500]\ntoo_high = 1000\ntoo_low = 20\n"), "{stored}");
+        assert_eq!(toml::from_str::<Answers>(&stored).unwrap(), answers);
+    }
+
+    #[test]
+    fn test_verify() {
+        let timer = Timer {
+            warmup: 0,
+            runs: 1,
+            timeout: None,
+        };
+        let day1 = registry::get(1).unwrap();
+        let day2 = registry::get(2).unwrap();
+        let mut results = vec![
+            run_day(day1, None, "24000\n\n1\n\n2\n", timer, |_| ()),
+            run_day(day2, Some(1), "A Y\nB X\n", timer, |_| ()),
+            failed_day(day2, Some(2), "no input"),
+        ];
+        let inputs = ["default", "default", "alice"].map(String::from);
+        let mut answers: Answers = toml::from_str(ANSWERS).unwrap();
+        let verification = answers.verify(&mut results, &inputs, false);
+        assert_eq!(
+            verification.to_string(),
+            "1 passed, 1 failed, 1 unknown, 1 errors"
+        );
+        assert!(!verification.is_success());
+        assert_eq!(results[0].parts[0].verdict, Some(Verdict::Pass));
+        assert_eq!(
+            results[0].parts[1].verdict,
+            Some(Verdict::Fail {
+                expected: Answer::Integer(45000)
+            })
+        );
+        assert_eq!(results[2].parts[0].verdict, None);
+        assert_eq!(answers.get(2, "default", 1), None);
+
+        let verification = answers.verify(&mut results[1..2], &inputs[1..2], true);
+        assert_eq!(verification.recorded, 1);
+        assert!(verification.is_success());
+        assert_eq!(answers.get(2, "default", 1), Some(&Answer::Integer(9)));
+        let verification = answers.verify(&mut results[1..2], &inputs[1..2], true);
+        assert_eq!((verification.passed, verification.recorded), (1, 0));
+    }
+
+    #[test]
+    fn test_load_missing() {
+        let answers = Answers::load(Path::new("does/not/exist.toml")).unwrap();
+        assert_eq!(answers, Answers::default());
+    }
+}
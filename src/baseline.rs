@@ -0,0 +1,323 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::runner::{DayResult, Measurement};
+
+/// Environment variable that overrides the default baselines directory.
+pub const BASELINE_DIR_ENV: &str = "AOC_BASELINE_DIR";
+pub const DEFAULT_BASELINE_DIR: &str = "baselines";
+
+/// Benchmark results saved under a name, keyed by day and part, e.g.
+///
+/// ```toml
+/// [day7.parse]
+/// median_ns = 20318
+/// p95_ns = 21407
+/// input_bytes = 10594
+///
+/// [day7.part1]
+/// median_ns = 284
+/// p95_ns = 296
+/// input_bytes = 10594
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Baseline {
+    days: BTreeMap<String, BTreeMap<String, Timing>>,
+}
+
+/// The saved times of a single part or of parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub median_ns: u64,
+    pub p95_ns: u64,
+    pub input_bytes: usize,
+}
+
+impl Timing {
+    fn new(time: &Measurement, input_bytes: usize) -> Self {
+        let nanos = |d: Duration| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX);
+        Self {
+            median_ns: nanos(time.median()),
+            p95_ns: nanos(time.percentile(95)),
+            input_bytes,
+        }
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+}
+
+impl Baseline {
+    /// The file of the baseline called `name`.
+    pub fn path(dir: &Path, name: &str) -> PathBuf {
+        dir.join(format!("{name}.toml"))
+    }
+
+    /// The measured parts of `results`, parts that could not be run are left out.
+    pub fn from_results(results: &[DayResult]) -> Self {
+        let mut baseline = Self::default();
+        for (day, name, time) in timings(results) {
+            baseline
+                .days
+                .entry(day_key(day.day))
+                .or_default()
+                .insert(name.to_string(), Timing::new(time, day.input_len));
+        }
+        baseline
+    }
+
+    pub fn load(path: &Path) -> Result<Self, BaselineError> {
+        let content = std::fs::read_to_string(path).map_err(|source| BaselineError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        toml::from_str(&content).map_err(|source| BaselineError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Saves the baseline, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> Result<(), BaselineError> {
+        let content = toml::to_string(self).expect("baselines are serializable");
+        path.parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(path, content))
+            .map_err(|source| BaselineError::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+    }
+
+    pub fn get(&self, day: u8, name: &str) -> Option<&Timing> {
+        self.days.get(&day_key(day))?.get(name)
+    }
+
+    /// Compares the median of every measured part of `results` with the
+    /// baseline. Changes of at most `threshold` percent are noise.
+    pub fn compare<'a>(&self, results: &'a [DayResult], threshold: f64) -> Vec<Comparison<'a>> {
+        timings(results)
+            .map(|(day, name, time)| {
+                let current = Timing::new(time, day.input_len);
+                let baseline = self.get(day.day, name).copied();
+                Comparison {
+                    day: day.day,
+                    name,
+                    change: Change::new(baseline, current, threshold),
+                    baseline,
+                    current,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Parsing and every part of every day that has a measurement.
+fn timings(results: &[DayResult]) -> impl Iterator<Item = (&DayResult, &str, &Measurement)> {
+    results.iter().flat_map(|day| {
+        let parse = day.parse.as_ref().map(|p| (day, "parse", p));
+        let parts = day
+            .parts
+            .iter()
+            .filter_map(move |p| Some((day, p.part.name, p.time.as_ref()?)));
+        parse.into_iter().chain(parts)
+    })
+}
+
+fn day_key(day: u8) -> String {
+    format!("day{day}")
+}
+
+/// A part of the current run compared with the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison<'a> {
+    pub day: u8,
+    /// `parse` or the name of the part.
+    pub name: &'a str,
+    pub baseline: Option<Timing>,
+    pub current: Timing,
+    pub change: Change,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    /// The relative change of the median is within the threshold.
+    Unchanged(f64),
+    Slower(f64),
+    Faster(f64),
+    /// The part is not in the baseline.
+    New,
+    /// The baseline was measured on an input of another size.
+    InputChanged,
+}
+
+impl Change {
+    fn new(baseline: Option<Timing>, current: Timing, threshold: f64) -> Self {
+        let Some(baseline) = baseline else {
+            return Change::New;
+        };
+        if baseline.input_bytes != current.input_bytes {
+            return Change::InputChanged;
+        }
+        // a baseline of 0ns only happens with a coarse clock
+        let baseline_ns = baseline.median_ns.max(1) as f64;
+        let change = (current.median_ns as f64 - baseline_ns) / baseline_ns * 100.0;
+        if change > threshold {
+            Change::Slower(change)
+        } else if change < -threshold {
+            Change::Faster(change)
+        } else {
+            Change::Unchanged(change)
+        }
+    }
+
+    pub fn is_regression(&self) -> bool {
+        matches!(self, Change::Slower(_))
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Unchanged(change) => write!(f, "{change:+.1}%"),
+            Change::Slower(change) => write!(f, "{change:+.1}% SLOWER"),
+            Change::Faster(change) => write!(f, "{change:+.1}% FASTER"),
+            Change::New => write!(f, "NEW"),
+            Change::InputChanged => write!(f, "INPUT CHANGED"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum BaselineError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+}
+
+impl Display for BaselineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BaselineError::Io { path, source } => {
+                write!(f, "cannot access baseline {}: {source}", path.display())
+            }
+            BaselineError::Parse { path, source } => {
+                write!(f, "invalid baseline {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for BaselineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BaselineError::Io { source, .. } => Some(source),
+            BaselineError::Parse { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use crate::runner::{run_day, Timer};
+
+    fn timing(median_ns: u64) -> Timing {
+        Timing {
+            median_ns,
+            p95_ns: median_ns,
+            input_bytes: 10,
+        }
+    }
+
+    #[test]
+    fn test_change() {
+        let baseline = Some(timing(1000));
+        assert_eq!(
+            Change::new(baseline, timing(1040), 5.0),
+            Change::Unchanged(4.0)
+        );
+        assert_eq!(
+            Change::new(baseline, timing(1100), 5.0),
+            Change::Slower(10.0)
+        );
+        assert_eq!(
+            Change::new(baseline, timing(500), 5.0),
+            Change::Faster(-50.0)
+        );
+        assert_eq!(Change::new(None, timing(500), 5.0), Change::New);
+        let other_input = Timing {
+            input_bytes: 11,
+            ..timing(1000)
+        };
+        assert_eq!(
+            Change::new(baseline, other_input, 5.0),
+            Change::InputChanged
+        );
+        assert!(Change::Slower(10.0).is_regression());
+        assert!(!Change::InputChanged.is_regression());
+        assert_eq!(Change::Slower(10.0).to_string(), "+10.0% SLOWER");
+        assert_eq!(Change::Unchanged(-1.25).to_string(), "-1.2%");
+    }
+
+    #[test]
+    fn test_compare() {
+        let timer = Timer {
+            warmup: 0,
+            runs: 3,
+            timeout: None,
+        };
+        let input = "$ cd /\n40000001 a\n";
+        let results = vec![run_day(
+            registry::get(7).unwrap(),
+            None,
+            input,
+            timer,
+            |_| (),
+        )];
+        let mut baseline = Baseline::from_results(&results);
+        assert_eq!(baseline.get(7, "parse").unwrap().input_bytes, input.len());
+        assert!(baseline.get(7, "part2").is_some());
+
+        baseline.days.get_mut("day7").unwrap().remove("part2");
+        baseline.days.get_mut("day7").unwrap().insert(
+            "part1".to_string(),
+            Timing {
+                median_ns: 0,
+                p95_ns: 0,
+                input_bytes: input.len(),
+            },
+        );
+        let comparisons = baseline.compare(&results, 5.0);
+        let names = comparisons.iter().map(|c| c.name).collect::<Vec<_>>();
+        assert_eq!(names, ["parse", "part1", "part2"]);
+        assert!(comparisons[1].change.is_regression());
+        assert_eq!(comparisons[2].change, Change::New);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline
+            .days
+            .entry(day_key(3))
+            .or_default()
+            .insert("part2_simd".to_string(), timing(1234));
+        let stored = toml::to_string(&baseline).unwrap();
+        assert!(stored.contains("[day3.part2_simd]"), "{stored}");
+        assert_eq!(toml::from_str::<Baseline>(&stored).unwrap(), baseline);
+        assert!(Baseline::load(Path::new("does/not/exist.toml")).is_err());
+    }
+}
@@ -0,0 +1,512 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use clap::builder::RangedU64ValueParser;
+use clap::{Args, Parser, Subcommand};
+
+use aoc::baseline::{BASELINE_DIR_ENV, DEFAULT_BASELINE_DIR};
+use aoc::fetch::{CONFIG_ENV, DEFAULT_CONFIG_FILE};
+use aoc::input::INPUT_DIR_ENV;
+use aoc::readme::README_FILE;
+use aoc::report::Format;
+use aoc::scaffold::DEFAULT_SRC_DIR;
+
+pub const FIRST_DAY: u8 = 1;
+pub const LAST_DAY: u8 = 25;
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc", version, about = "Advent of Code 2022 solutions")]
+pub struct Cli {
+    /// Directory containing the inputs as `dayN.txt`.
+    #[arg(long, global = true, env = INPUT_DIR_ENV, value_name = "DIR")]
+    pub input_dir: Option<PathBuf>,
+
+    /// File with the known answers [default: answers.toml in the inputs directory]
+    #[arg(long, global = true, env = "AOC_ANSWERS_FILE", value_name = "PATH")]
+    pub answers: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Solve one or more days.
+    Run(RunArgs),
+    /// Check the answers of one or more days against the answers file.
+    Verify(VerifyArgs),
+    /// Measure the time distribution and throughput of every part.
+    Bench(BenchArgs),
+    /// Benchmark every day and rewrite the performance section of the README.
+    Readme(ReadmeArgs),
+    /// Download the inputs of one or more days into the inputs directory.
+    Fetch(FetchArgs),
+    /// Solve a part and submit its answer.
+    Submit(SubmitArgs),
+    /// Create and register the module of a new day.
+    New(NewArgs),
+    /// Print a random input of a day.
+    Gen(GenArgs),
+}
+
+/// Which days and parts to run on which input.
+#[derive(Debug, Args)]
+pub struct Selection {
+    /// Days to run, e.g. `7`, `1,3,5`, `3..=9` or `3..10`.
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    pub days: Option<Days>,
+
+    /// Run every day.
+    #[arg(long)]
+    pub all: bool,
+
+    /// Only run this part.
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// Read the input from this file instead of the inputs directory,
+    /// `-` for stdin. Only valid if a single day is selected.
+    #[arg(short, long, value_name = "PATH")]
+    pub input: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// Solve up to this many days at the same time.
+    #[arg(short, long, default_value_t = 1, value_name = "N",
+          value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub jobs: usize,
+
+    /// Give up on a part if a single run takes longer than this.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+    pub timeout: Option<Duration>,
+
+    /// Unmeasured runs of every part before measuring it.
+    #[arg(long, default_value_t = 1, value_name = "N")]
+    pub warmup: u32,
+
+    /// Measured runs of every part.
+    #[arg(long, default_value_t = 10, value_name = "N",
+          value_parser = clap::value_parser!(u32).range(1..))]
+    pub runs: u32,
+
+    /// How the results are reported.
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// Solve up to this many days at the same time.
+    #[arg(short, long, default_value_t = 1, value_name = "N",
+          value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub jobs: usize,
+
+    /// Give up on a part if a single run takes longer than this.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+    pub timeout: Option<Duration>,
+
+    /// Store the answers of parts that don't have a known answer yet.
+    #[arg(long)]
+    pub record: bool,
+
+    /// Solve every input in `<DIR>/dayN/*.txt` instead of `dayN.txt`, the
+    /// answers of an input are stored under its file stem.
+    #[arg(long, conflicts_with = "input")]
+    pub corpus: bool,
+
+    /// How the results are reported.
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// Also measure the wall-clock time of solving up to this many days at
+    /// the same time. The parts are measured one day after the other, so
+    /// that they don't compete for the CPU.
+    #[arg(short, long, default_value_t = 1, value_name = "N",
+          value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub jobs: usize,
+
+    /// Give up on a part if a single run takes longer than this.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+    pub timeout: Option<Duration>,
+
+    /// Unmeasured runs of every part before measuring it.
+    #[arg(long, default_value_t = 10, value_name = "N")]
+    pub warmup: u32,
+
+    /// Measured runs of every part.
+    #[arg(long, default_value_t = 100, value_name = "N",
+          value_parser = clap::value_parser!(u32).range(1..))]
+    pub runs: u32,
+
+    /// How the results are reported.
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: Format,
+
+    /// Save the results as the baseline with this name.
+    #[arg(long, value_name = "NAME")]
+    pub save: Option<String>,
+
+    /// Compare the results with the baseline with this name, fails if a
+    /// part got slower.
+    #[arg(long, value_name = "NAME")]
+    pub compare: Option<String>,
+
+    /// Changes of the median of at most this many percent are noise.
+    #[arg(long, default_value_t = 5.0, value_name = "PERCENT")]
+    pub threshold: f64,
+
+    /// Directory containing the baselines as `<NAME>.toml`.
+    #[arg(long, env = BASELINE_DIR_ENV, default_value = DEFAULT_BASELINE_DIR, value_name = "DIR")]
+    pub baseline_dir: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct ReadmeArgs {
+    /// Unmeasured runs of every part before measuring it.
+    #[arg(long, default_value_t = 10, value_name = "N")]
+    pub warmup: u32,
+
+    /// Measured runs of every part.
+    #[arg(long, default_value_t = 100, value_name = "N",
+          value_parser = clap::value_parser!(u32).range(1..))]
+    pub runs: u32,
+
+    /// Description of the machine the results are taken on [default: the CPU].
+    #[arg(long, env = "AOC_MACHINE")]
+    pub machine: Option<String>,
+
+    /// The README to update.
+    #[arg(long, default_value = README_FILE, value_name = "PATH")]
+    pub readme: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct FetchArgs {
+    /// Days to fetch, e.g. `7`, `1,3,5`, `3..=9` or `3..10`.
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    pub days: Option<Days>,
+
+    /// Fetch every day.
+    #[arg(long)]
+    pub all: bool,
+
+    #[command(flatten)]
+    pub client: ClientArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct SubmitArgs {
+    /// The day to solve.
+    #[arg(value_parser = clap::value_parser!(u8).range(FIRST_DAY as i64..=LAST_DAY as i64))]
+    pub day: u8,
+
+    /// The part to solve.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: u8,
+
+    /// Read the input from this file instead of the inputs directory,
+    /// `-` for stdin.
+    #[arg(short, long, value_name = "PATH")]
+    pub input: Option<String>,
+
+    #[command(flatten)]
+    pub client: ClientArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct NewArgs {
+    /// The day to create.
+    #[arg(value_parser = clap::value_parser!(u8).range(FIRST_DAY as i64..=LAST_DAY as i64))]
+    pub day: u8,
+
+    /// The title of the puzzle, for the module documentation.
+    #[arg(long)]
+    pub title: Option<String>,
+
+    /// Directory with `lib.rs` and `registry.rs`.
+    #[arg(long, default_value = DEFAULT_SRC_DIR, value_name = "DIR")]
+    pub src_dir: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct GenArgs {
+    /// The day to generate an input for.
+    #[arg(value_parser = clap::value_parser!(u8).range(FIRST_DAY as i64..=LAST_DAY as i64))]
+    pub day: u8,
+
+    /// The same seed and size always result in the same input.
+    #[arg(long, default_value_t = 0, value_name = "N")]
+    pub seed: u64,
+
+    /// How big the input is, what it counts depends on the day [default: the size of a real input]
+    #[arg(long, value_name = "N")]
+    pub size: Option<usize>,
+}
+
+/// How to reach the website.
+#[derive(Debug, Args)]
+pub struct ClientArgs {
+    /// Where the puzzles are hosted [default: `base_url` of the config or https://adventofcode.com]
+    #[arg(long, env = "AOC_BASE_URL", value_name = "URL")]
+    pub base_url: Option<String>,
+
+    /// Config file with the session token.
+    #[arg(long, env = CONFIG_ENV, default_value = DEFAULT_CONFIG_FILE, value_name = "PATH")]
+    pub config: PathBuf,
+}
+
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    match s.parse::<f64>().map(Duration::try_from_secs_f64) {
+        Ok(Ok(timeout)) if !timeout.is_zero() => Ok(timeout),
+        _ => Err(format!("'{s}' is not a positive number of seconds")),
+    }
+}
+
+/// A sorted, deduplicated selection of days.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Days(Vec<u8>);
+
+impl Days {
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn single(&self) -> Option<u8> {
+        match self.0.as_slice() {
+            [day] => Some(*day),
+            _ => None,
+        }
+    }
+}
+
+impl FromIterator<u8> for Days {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut days = iter.into_iter().collect::<Vec<_>>();
+        days.sort_unstable();
+        days.dedup();
+        Self(days)
+    }
+}
+
+impl FromStr for Days {
+    type Err = DaysError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = Vec::new();
+        for part in s.split(',').map(str::trim) {
+            if let Some((start, end)) = part.split_once("..=") {
+                days.extend(parse_day(start)?..=parse_day(end)?);
+            } else if let Some((start, end)) = part.split_once("..") {
+                days.extend(parse_day(start)?..parse_day(end)?);
+            } else {
+                days.push(parse_day(part)?);
+            }
+        }
+        days.sort_unstable();
+        days.dedup();
+        if days.is_empty() {
+            return Err(DaysError::Empty(s.to_string()));
+        }
+        Ok(Self(days))
+    }
+}
+
+fn parse_day(s: &str) -> Result<u8, DaysError> {
+    match s.trim().parse::<u8>() {
+        Ok(day) if (FIRST_DAY..=LAST_DAY).contains(&day) => Ok(day),
+        _ => Err(DaysError::InvalidDay(s.to_string())),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaysError {
+    InvalidDay(String),
+    Empty(String),
+}
+
+impl Display for DaysError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaysError::InvalidDay(s) => {
+                write!(f, "'{s}' is not a day between {FIRST_DAY} and {LAST_DAY}")
+            }
+            DaysError::Empty(s) => write!(f, "'{s}' does not select any day"),
+        }
+    }
+}
+
+impl std::error::Error for DaysError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_days_from_str() {
+        assert_eq!("7".parse(), Ok(Days(vec![7])));
+        assert_eq!("3..=5".parse(), Ok(Days(vec![3, 4, 5])));
+        assert_eq!("3..5".parse(), Ok(Days(vec![3, 4])));
+        assert_eq!("9,1,3..=4,1".parse(), Ok(Days(vec![1, 3, 4, 9])));
+        assert_eq!(
+            "0".parse::<Days>(),
+            Err(DaysError::InvalidDay("0".to_string()))
+        );
+        assert_eq!(
+            "5..5".parse::<Days>(),
+            Err(DaysError::Empty("5..5".to_string()))
+        );
+        assert!("x".parse::<Days>().is_err());
+    }
+
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_run_args() {
+        let cli = Cli::try_parse_from(["aoc", "run", "7", "--part", "2"]).unwrap();
+        let Command::Run(args) = cli.command else {
+            panic!("expected the run command");
+        };
+        assert_eq!(args.selection.days, Some(Days(vec![7])));
+        assert_eq!(args.selection.part, Some(2));
+        assert_eq!(args.jobs, 1);
+        assert_eq!(args.timeout, None);
+
+        let cli = Cli::try_parse_from(["aoc", "run", "7", "--timeout", "2.5"]).unwrap();
+        let Command::Run(args) = cli.command else {
+            panic!("expected the run command");
+        };
+        assert_eq!(args.timeout, Some(Duration::from_millis(2500)));
+        assert!(Cli::try_parse_from(["aoc", "run", "7", "--timeout", "0"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "7", "--timeout", "-1"]).is_err());
+
+        assert!(Cli::try_parse_from(["aoc", "run"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--all", "3"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "3", "--part", "3"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--all", "--jobs", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_verify_args() {
+        let cli = Cli::try_parse_from(["aoc", "verify", "--all", "--record"]).unwrap();
+        let Command::Verify(args) = cli.command else {
+            panic!("expected the verify command");
+        };
+        assert!(args.selection.all);
+        assert!(args.record);
+        assert!(!args.corpus);
+        assert!(Cli::try_parse_from(["aoc", "verify", "--all", "--corpus"]).is_ok());
+        assert!(Cli::try_parse_from(["aoc", "verify", "3", "--corpus", "-i", "a.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_bench_args() {
+        let cli = Cli::try_parse_from(["aoc", "bench", "1..=3"]).unwrap();
+        let Command::Bench(args) = cli.command else {
+            panic!("expected the bench command");
+        };
+        assert_eq!(args.selection.days, Some(Days(vec![1, 2, 3])));
+        assert_eq!((args.warmup, args.runs), (10, 100));
+        assert_eq!(args.threshold, 5.0);
+        assert!(Cli::try_parse_from(["aoc", "bench", "--all", "--jobs", "0"]).is_err());
+
+        let cli = Cli::try_parse_from([
+            "aoc",
+            "bench",
+            "--all",
+            "--compare",
+            "main",
+            "--save",
+            "new",
+            "--threshold",
+            "10",
+        ])
+        .unwrap();
+        let Command::Bench(args) = cli.command else {
+            panic!("expected the bench command");
+        };
+        assert_eq!(args.compare.as_deref(), Some("main"));
+        assert_eq!(args.save.as_deref(), Some("new"));
+        assert_eq!(args.threshold, 10.0);
+    }
+
+    #[test]
+    fn test_readme_args() {
+        let cli = Cli::try_parse_from(["aoc", "readme", "--machine", "M2"]).unwrap();
+        let Command::Readme(args) = cli.command else {
+            panic!("expected the readme command");
+        };
+        assert_eq!(args.machine.as_deref(), Some("M2"));
+        assert_eq!(args.readme, PathBuf::from("README.md"));
+    }
+
+    #[test]
+    fn test_fetch_args() {
+        let cli = Cli::try_parse_from(["aoc", "fetch", "1..=3", "--base-url", "http://localhost"])
+            .unwrap();
+        let Command::Fetch(args) = cli.command else {
+            panic!("expected the fetch command");
+        };
+        assert_eq!(args.days, Some(Days(vec![1, 2, 3])));
+        assert_eq!(args.client.base_url.as_deref(), Some("http://localhost"));
+        assert!(Cli::try_parse_from(["aoc", "fetch"]).is_err());
+    }
+
+    #[test]
+    fn test_submit_args() {
+        let cli = Cli::try_parse_from(["aoc", "submit", "10", "2", "-i", "-"]).unwrap();
+        let Command::Submit(args) = cli.command else {
+            panic!("expected the submit command");
+        };
+        assert_eq!((args.day, args.part), (10, 2));
+        assert_eq!(args.input.as_deref(), Some("-"));
+        assert!(Cli::try_parse_from(["aoc", "submit", "10", "3"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "submit", "26", "1"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "submit", "10"]).is_err());
+    }
+
+    #[test]
+    fn test_new_args() {
+        let cli =
+            Cli::try_parse_from(["aoc", "new", "15", "--title", "Beacon Exclusion Zone"]).unwrap();
+        let Command::New(args) = cli.command else {
+            panic!("expected the new command");
+        };
+        assert_eq!(args.day, 15);
+        assert_eq!(args.title.as_deref(), Some("Beacon Exclusion Zone"));
+        assert_eq!(args.src_dir, PathBuf::from("src"));
+        assert!(Cli::try_parse_from(["aoc", "new", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_gen_args() {
+        let cli = Cli::try_parse_from(["aoc", "gen", "9", "--seed", "3", "--size", "100"]).unwrap();
+        let Command::Gen(args) = cli.command else {
+            panic!("expected the gen command");
+        };
+        assert_eq!((args.day, args.seed, args.size), (9, 3, Some(100)));
+        let cli = Cli::try_parse_from(["aoc", "gen", "9"]).unwrap();
+        let Command::Gen(args) = cli.command else {
+            panic!("expected the gen command");
+        };
+        assert_eq!((args.seed, args.size), (0, None));
+    }
+}
@@ -1,22 +1,32 @@
-const INPUT: &str = include_str!("../inputs/day1.txt");
+//! Day 1: Calorie Counting
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-enum Value {
+use itertools::process_results;
+
+use crate::answer::Answer;
+use crate::parse::{self, Cursor, ParseError};
+use crate::solution::Solution;
+
+/// A line of the input, either the calories of an item or the empty line
+/// between two elves.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Value {
     Value(isize),
     Separator,
 }
 
-impl From<&str> for Value {
-    fn from(v: &str) -> Self {
-        if v.is_empty() {
-            Self::Separator
-        } else {
-            Self::Value(v.parse().unwrap())
+impl Value {
+    pub fn from_line(mut line: Cursor<'_>) -> Result<Self, ParseError> {
+        if line.is_empty() {
+            return Ok(Self::Separator);
         }
+        let v = line.number()?;
+        line.end()?;
+        Ok(Self::Value(v))
     }
 }
 
-struct CaloricChunks<I>
+/// Sums up the values of every elf.
+pub struct CaloricChunks<I>
 where
     I: Iterator<Item = Value>,
 {
@@ -55,21 +65,45 @@ where
     }
 }
 
-pub fn part1() -> isize {
-    CaloricChunks::from(INPUT.lines().map(Value::from))
-        .max()
-        .unwrap()
+/// Every line of the input as a [`Value`].
+pub fn values(input: &str) -> impl Iterator<Item = Result<Value, ParseError>> + '_ {
+    parse::lines(input).map(Value::from_line)
+}
+
+pub fn part1(input: &str) -> Result<isize, ParseError> {
+    process_results(values(input), |values| CaloricChunks::from(values).max())?
+        .ok_or_else(|| ParseError::end_of_input(input, "a number"))
 }
 
-pub fn part2() -> isize {
+pub fn part2(input: &str) -> Result<isize, ParseError> {
+    let biggest = biggest_three(input)?;
+    Ok(biggest.iter().sum())
+}
+
+/// Both parts in a single pass over the input, the biggest elf is one of
+/// the three biggest.
+pub fn solve(input: &str) -> Result<(isize, isize), ParseError> {
+    let biggest = biggest_three(input)?;
+    let max = biggest.iter().copied().max().expect("three elves");
+    Ok((max, biggest.iter().sum()))
+}
+
+fn biggest_three(input: &str) -> Result<[isize; 3], ParseError> {
     let mut biggest = [isize::MIN; 3];
-    CaloricChunks::from(INPUT.lines().map(Value::from)).for_each(|v| {
-        let index = get_index_of_lowest(&biggest);
-        if v > biggest[index] {
-            biggest[index] = v;
-        }
-    });
-    biggest.iter().sum()
+    let mut elves = 0;
+    process_results(values(input), |values| {
+        CaloricChunks::from(values).for_each(|v| {
+            elves += 1;
+            let index = get_index_of_lowest(&biggest);
+            if v > biggest[index] {
+                biggest[index] = v;
+            }
+        })
+    })?;
+    if elves < biggest.len() {
+        return Err(ParseError::end_of_input(input, "at least three elves"));
+    }
+    Ok(biggest)
 }
 
 fn get_index_of_lowest(values: &[isize]) -> usize {
@@ -84,30 +118,113 @@ fn get_index_of_lowest(values: &[isize]) -> usize {
     index
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Result<Answer, ParseError> {
+        part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &&str) -> Result<Answer, ParseError> {
+        part2(input).map(Answer::from)
+    }
+
+    fn solve(input: &&str) -> Result<(Answer, Answer), ParseError> {
+        let (part1, part2) = solve(input)?;
+        Ok((part1.into(), part2.into()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+    #[test]
+    fn test_example() {
+        assert_eq!(part1(EXAMPLE), Ok(24000));
+        assert_eq!(part2(EXAMPLE), Ok(45000));
+        assert_eq!(solve(EXAMPLE), Ok((24000, 45000)));
+        assert_eq!(
+            Day1::solve(&EXAMPLE),
+            Ok((Answer::Integer(24000), Answer::Integer(45000)))
+        );
+    }
+
+    #[test]
+    fn test_without_trailing_newline() {
+        assert_eq!(part1(EXAMPLE.trim_end()), Ok(24000));
+        assert_eq!(part2("1\n\n2\n\n3\n\n4"), Ok(9));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = part1("1000\n2x00\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 2, "`x00`"));
+        assert_eq!(part1("").unwrap_err().expected, "a number");
+        assert_eq!(part2("1\n\n2\n").unwrap_err().line, 4);
+        assert_eq!(solve("1\n\n2\n").unwrap_err().line, 4);
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 68442);
+        let Some(input) = test_input(1) else { return };
+        assert_eq!(part1(&input), Ok(68442));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(1) else { return };
+        b.iter(|| part1(&input));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), 204837);
+        let Some(input) = test_input(1) else { return };
+        assert_eq!(part2(&input), Ok(204837));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(1) else { return };
+        b.iter(|| part2(&input));
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_solve(b: &mut Bencher) {
+        let Some(input) = test_input(1) else { return };
+        b.iter(|| solve(&input));
     }
 }
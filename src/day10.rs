@@ -1,19 +1,33 @@
+//! Day 10: Cathode-Ray Tube
+
 use std::fmt::{Debug, Display, Formatter, Write};
 
-const INPUT: &str = include_str!("../inputs/day10.txt");
+use crate::answer::Answer;
+use crate::parse::{self, Cursor, ParseError};
+use crate::solution::Solution;
 
-enum Command {
+/// An instruction of the program.
+#[derive(Debug)]
+pub enum Command {
     Addx(isize),
     Noop,
 }
 
 impl Command {
-    fn from_str(s: &str) -> Self {
-        match s.as_bytes()[0] {
-            b'a' => Command::Addx(s[5..].parse().unwrap()),
-            b'n' => Command::Noop,
-            _ => unreachable!(),
-        }
+    fn from_line(mut line: Cursor<'_>) -> Result<Self, ParseError> {
+        let command = match line.peek() {
+            Some(b'a') => {
+                line.literal("addx ")?;
+                Command::Addx(line.number()?)
+            }
+            Some(b'n') => {
+                line.literal("noop")?;
+                Command::Noop
+            }
+            _ => return Err(line.error("`addx` or `noop`")),
+        };
+        line.end()?;
+        Ok(command)
     }
 
     const fn cycle_count(&self) -> usize {
@@ -31,13 +45,16 @@ impl Command {
     }
 }
 
-pub fn part1() -> isize {
+pub fn parse_program(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse::lines(input).map(Command::from_line).collect()
+}
+
+pub fn part1(program: &[Command]) -> isize {
     let mut x: isize = 1;
     let mut cycle: usize = 0;
     let mut target_cycles = [20_usize, 60, 100, 140, 180, 220].iter().peekable();
     let mut total_signal_strength: isize = 0;
-    let commands = INPUT.lines().map(Command::from_str);
-    for c in commands {
+    for c in program {
         cycle += c.cycle_count();
         if let Some(&&target_cycle) = target_cycles.peek() {
             if cycle >= target_cycle {
@@ -56,6 +73,7 @@ pub fn part1() -> isize {
 const LINE_WIDTH: usize = 40;
 const LINES: usize = 6;
 
+/// The pixels drawn by the CRT, decoded to letters by [`crate::ocr`].
 pub struct CRTScreen {
     lines: [[u8; LINE_WIDTH]; LINES],
 }
@@ -81,20 +99,35 @@ impl Display for CRTScreen {
     }
 }
 
+impl From<CRTScreen> for Answer {
+    fn from(screen: CRTScreen) -> Self {
+        let rendered = screen
+            .lines
+            .iter()
+            .flat_map(|l| l.iter().copied().chain(std::iter::once(b'\n')))
+            .map(char::from)
+            .collect::<String>();
+        Answer::grid(rendered)
+    }
+}
+
 impl Debug for CRTScreen {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         Display::fmt(self, f)
     }
 }
 
-pub fn part2() -> CRTScreen {
+pub fn part2(program: &[Command]) -> CRTScreen {
     let mut screen = CRTScreen::new();
 
     let mut sprite_center: isize = 1;
     let mut cycle: usize = 0;
-    INPUT.lines().map(Command::from_str).for_each(|c| {
-        let cycle_count = c.cycle_count();
-        for _ in 0..cycle_count {
+    for c in program {
+        for _ in 0..c.cycle_count() {
+            // the screen is full, the rest of the program isn't drawn
+            if cycle >= LINES * LINE_WIDTH {
+                break;
+            }
             let (line_index, line_x) = (cycle / LINE_WIDTH, cycle % LINE_WIDTH);
             let line = &mut screen.lines[line_index];
             if (sprite_center - 1..=sprite_center + 1).contains(&(line_x as isize)) {
@@ -104,33 +137,256 @@ pub fn part2() -> CRTScreen {
         }
 
         c.apply(&mut sprite_center)
-    });
+    }
 
     screen
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Parsed<'a> = Vec<Command>;
+
+    const HAS_PARSE_STEP: bool = true;
+
+    fn parse(input: &str) -> Result<Vec<Command>, ParseError> {
+        parse_program(input)
+    }
+
+    fn part1(program: &Vec<Command>) -> Result<Answer, ParseError> {
+        Ok(part1(program).into())
+    }
+
+    fn part2(program: &Vec<Command>) -> Result<Answer, ParseError> {
+        Ok(part2(program).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
 
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop
+";
+
+    #[test]
+    fn test_example() {
+        let program = parse_program(EXAMPLE).unwrap();
+        assert_eq!(part1(&program), 13140);
+        assert_eq!(
+            part2(&program).to_string(),
+            r#"CRTScreen:
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+"#
+        );
+    }
+
+    #[test]
+    fn test_command_from_str() {
+        let command = |s| Command::from_line(Cursor::new(0, s));
+        assert!(matches!(command("noop"), Ok(Command::Noop)));
+        assert!(matches!(command("addx 15"), Ok(Command::Addx(15))));
+        assert!(matches!(command("addx -11"), Ok(Command::Addx(-11))));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_program("noop\naddx\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 1, "`addx `"));
+        let e = parse_program("noop\nnoop 1\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 5, "` `"));
+        assert_eq!(parse_program("mul 3").unwrap_err().found, "`mul`");
+    }
+
+    #[test]
+    fn test_longer_than_the_screen() {
+        let screen = |input: &str| part2(&parse_program(input).unwrap()).to_string();
+        let full = screen(&"noop\n".repeat(240));
+        assert_eq!(screen(&"noop\n".repeat(241)), full);
+        assert_eq!(screen(&("noop\n".repeat(240) + "addx 5\n")), full);
+        assert_eq!(
+            part1(&parse_program(&"addx 1\n".repeat(200)).unwrap()),
+            57200
+        );
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 13480);
+        let Some(input) = test_input(10) else { return };
+        assert_eq!(part1(&parse_program(&input).unwrap()), 13480);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(10) else { return };
+        b.iter(|| part1(&parse_program(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
+        let Some(input) = test_input(10) else { return };
         assert_eq!(
-            part2().to_string(),
+            part2(&parse_program(&input).unwrap()).to_string(),
             // the letters are 'EGJBGCFK'
             r#"CRTScreen:
 ####..##....##.###...##...##..####.#..#.
@@ -143,8 +399,10 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(10) else { return };
+        b.iter(|| part2(&parse_program(&input).unwrap()));
     }
 }
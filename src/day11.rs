@@ -1,8 +1,12 @@
+//! Day 11: Monkey in the Middle
+
 use std::collections::VecDeque;
 
-const INPUT: &str = include_str!("../inputs/day11.txt");
+use crate::answer::Answer;
+use crate::parse::{self, Cursor, ParseError};
+use crate::solution::Solution;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Operation {
     /// new = old + n
     Add(usize),
@@ -12,7 +16,9 @@ enum Operation {
     Exp,
 }
 
-struct Monkey {
+/// A monkey with the items it holds and how it passes them on.
+#[derive(Debug, Clone)]
+pub struct Monkey {
     starting_items: VecDeque<usize>,
     operation: Operation,
     test_divisible_by: usize,
@@ -20,67 +26,141 @@ struct Monkey {
     test_false: usize,
 }
 
+/// The next line, which must be there.
+fn next_line<'a>(
+    input: &str,
+    lines: &mut impl Iterator<Item = Cursor<'a>>,
+    expected: &str,
+) -> Result<Cursor<'a>, ParseError> {
+    lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, expected))
+}
+
 impl Monkey {
-    fn from_str_array(s: [&str; 7]) -> Self {
-        let starting_items = s[1][18..]
-            .as_bytes()
-            .array_chunks::<2>()
-            .step_by(2)
-            .map(|b| unsafe {
-                // SAFETY: the bytes came from a ASCII &str, so they are valid UTF-8
-                std::str::from_utf8_unchecked(b)
-            })
-            .map(|n| n.parse::<usize>().unwrap())
-            .collect::<VecDeque<usize>>();
-        let s2_bytes = s[2].as_bytes();
-        let operation = match s2_bytes[23] {
-            b'+' => Operation::Add(s[2][25..].parse::<usize>().unwrap()),
-            b'*' => {
-                if s2_bytes[25] == b'o' {
-                    Operation::Exp
-                } else {
-                    Operation::Mul(s[2][25..].parse::<usize>().unwrap())
-                }
+    /// Parses the six lines of the monkey with the given number.
+    fn parse<'a>(
+        input: &str,
+        number: usize,
+        lines: &mut impl Iterator<Item = Cursor<'a>>,
+    ) -> Result<Self, ParseError> {
+        let mut l = next_line(input, lines, "`Monkey `")?;
+        l.literal("Monkey ")?;
+        let column = l.column();
+        if l.number::<usize>()? != number {
+            return Err(l.error_at(column, format!("monkey {number}")));
+        }
+        l.literal(":")?;
+        l.end()?;
+
+        let mut l = next_line(input, lines, "`  Starting items: `")?;
+        l.literal("  Starting items: ")?;
+        let mut starting_items = VecDeque::new();
+        while !l.is_empty() {
+            if !starting_items.is_empty() {
+                l.literal(", ")?;
             }
-            _ => unreachable!(),
+            starting_items.push_back(l.number()?);
+        }
+
+        let mut l = next_line(input, lines, "`  Operation: `")?;
+        l.literal("  Operation: new = old ")?;
+        let operator = l.one_of(b"+*")?;
+        l.literal(" ")?;
+        let operation = match operator {
+            b'*' if l.literal("old").is_ok() => Operation::Exp,
+            b'*' => Operation::Mul(l.number()?),
+            _ => Operation::Add(l.number()?),
         };
-        let test_divisible_by = s[3][21..].parse::<usize>().unwrap();
-        let test_true = s[4][29..].parse::<usize>().unwrap();
-        let test_false = s[5][30..].parse::<usize>().unwrap();
-        Self {
+        l.end()?;
+
+        let mut l = next_line(input, lines, "`  Test: `")?;
+        l.literal("  Test: divisible by ")?;
+        let column = l.column();
+        let test_divisible_by = l.number()?;
+        if test_divisible_by == 0 {
+            return Err(l.error_at(column, "a divisor greater than 0"));
+        }
+        l.end()?;
+
+        let mut l = next_line(input, lines, "`    If true: `")?;
+        l.literal("    If true: throw to monkey ")?;
+        let test_true = l.number()?;
+        l.end()?;
+
+        let mut l = next_line(input, lines, "`    If false: `")?;
+        l.literal("    If false: throw to monkey ")?;
+        let test_false = l.number()?;
+        l.end()?;
+
+        Ok(Self {
             starting_items,
             operation,
             test_divisible_by,
             test_true,
             test_false,
-        }
+        })
     }
 }
 
-fn parse_monkeys() -> Vec<Monkey> {
-    INPUT
-        .lines()
-        .chain(std::iter::once("")) // array chunks doesn't like the single newline at the end, so we add one
-        .array_chunks::<7>()
-        .map(Monkey::from_str_array)
-        .collect::<Vec<Monkey>>()
+/// The monkeys in the order of their numbers.
+pub fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let mut lines = parse::lines(input);
+    let mut monkeys = Vec::new();
+    loop {
+        monkeys.push(Monkey::parse(input, monkeys.len(), &mut lines)?);
+        // monkeys are separated by an empty line
+        match lines.next() {
+            Some(l) => l.end()?,
+            None => break,
+        }
+    }
+
+    if monkeys.len() < 2 {
+        return Err(ParseError::end_of_input(input, "at least two monkeys"));
+    }
+    for (i, monkey) in monkeys.iter().enumerate() {
+        // every monkey takes exactly seven lines, so the targets are easy to find
+        let targets = [
+            (
+                monkey.test_true,
+                7 * i + 4,
+                "    If true: throw to monkey ".len(),
+            ),
+            (
+                monkey.test_false,
+                7 * i + 5,
+                "    If false: throw to monkey ".len(),
+            ),
+        ];
+        for (target, line, column) in targets {
+            if target >= monkeys.len() || target == i {
+                return Err(ParseError::new(
+                    line,
+                    column,
+                    format!("another monkey from 0 to {}", monkeys.len() - 1),
+                    format!("`{target}`"),
+                ));
+            }
+        }
+    }
+    Ok(monkeys)
 }
 
-pub fn part1() -> usize {
-    solve(parse_monkeys(), 20, |v| v / 3)
+pub fn part1(monkeys: &[Monkey]) -> usize {
+    solve(monkeys.to_vec(), 20, |v| v / 3)
 }
 
-pub fn part2() -> usize {
-    let monkeys = parse_monkeys();
+pub fn part2(monkeys: &[Monkey]) -> usize {
     let modulo: usize = monkeys.iter().map(|m| m.test_divisible_by).product();
-    solve(monkeys, 10_000, |v| v % modulo)
+    solve(monkeys.to_vec(), 10_000, |v| v % modulo)
 }
 
 fn solve<F>(mut monkeys: Vec<Monkey>, rounds: usize, worry_level_modifier: F) -> usize
 where
     F: Fn(usize) -> usize,
 {
-    let mut monkey_business = [0_usize; 8];
+    let mut monkey_business = vec![0_usize; monkeys.len()];
 
     for _ in 0..rounds {
         for monkey_num in 0..monkeys.len() {
@@ -102,7 +182,7 @@ where
                 item = worry_level_modifier(item);
 
                 // throw the item to the next monkey
-                let next_monkey = if item % monkeys[monkey_num].test_divisible_by == 0 {
+                let next_monkey = if item.is_multiple_of(monkeys[monkey_num].test_divisible_by) {
                     monkeys[monkey_num].test_true
                 } else {
                     monkeys[monkey_num].test_false
@@ -116,36 +196,138 @@ where
     monkey_business[monkey_business.len() - 1] * monkey_business[monkey_business.len() - 2]
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Parsed<'a> = Vec<Monkey>;
+
+    const HAS_PARSE_STEP: bool = true;
+
+    const PARSE_BENCH: Option<&'static str> = Some("bench_parse_monkeys");
+
+    fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
+        parse_monkeys(input)
+    }
+
+    fn part1(monkeys: &Vec<Monkey>) -> Result<Answer, ParseError> {
+        Ok(part1(monkeys).into())
+    }
+
+    fn part2(monkeys: &Vec<Monkey>) -> Result<Answer, ParseError> {
+        Ok(part2(monkeys).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
 
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn test_example() {
+        let monkeys = parse_monkeys(EXAMPLE).unwrap();
+        assert_eq!(part1(&monkeys), 10605);
+        assert_eq!(part2(&monkeys), 2713310158);
+    }
+
+    #[test]
+    fn test_parse_monkeys() {
+        let monkeys = parse_monkeys(EXAMPLE).unwrap();
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(monkeys[1].starting_items, [54, 65, 75, 74]);
+        assert!(matches!(monkeys[0].operation, Operation::Mul(19)));
+        assert!(matches!(monkeys[1].operation, Operation::Add(6)));
+        assert!(matches!(monkeys[2].operation, Operation::Exp));
+        assert_eq!(monkeys[3].test_divisible_by, 17);
+        assert_eq!((monkeys[3].test_true, monkeys[3].test_false), (0, 1));
+        // without the trailing newline
+        assert_eq!(parse_monkeys(EXAMPLE.trim_end()).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input = EXAMPLE.replace("old * old", "old / old");
+        let e = parse_monkeys(&input).unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (17, 24, "`/`"));
+        let input = EXAMPLE.replace("items: 54, 65", "items: 54 65");
+        let e = parse_monkeys(&input).unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (9, 21, "`, `"));
+        let input = EXAMPLE.replace("Monkey 2", "Monkey 3");
+        assert_eq!(parse_monkeys(&input).unwrap_err().expected, "monkey 2");
+        let input = EXAMPLE.replace("throw to monkey 3", "throw to monkey 4");
+        let e = parse_monkeys(&input).unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (6, 31, "`4`"));
+        let e = parse_monkeys(&EXAMPLE[..EXAMPLE.rfind("    If false").unwrap()]).unwrap_err();
+        assert_eq!((e.line, e.found.as_str()), (27, "end of input"));
+    }
+
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_parse_monkeys(b: &mut test::Bencher) {
-        b.iter(|| parse_monkeys());
+        let Some(input) = test_input(11) else { return };
+        b.iter(|| parse_monkeys(&input).unwrap());
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 316888);
+        let Some(input) = test_input(11) else { return };
+        assert_eq!(part1(&parse_monkeys(&input).unwrap()), 316888);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(11) else { return };
+        b.iter(|| part1(&parse_monkeys(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), 35270398814);
+        let Some(input) = test_input(11) else { return };
+        assert_eq!(part2(&parse_monkeys(&input).unwrap()), 35270398814);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(11) else { return };
+        b.iter(|| part2(&parse_monkeys(&input).unwrap()));
     }
 }
@@ -1,36 +1,92 @@
+//! Day 12: Hill Climbing Algorithm
+
 use pathfinding::matrix::Matrix;
 use pathfinding::prelude::bfs;
 
-const INPUT: &str = include_str!("../inputs/day12.txt");
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+/// The elevations with the start and end positions.
+#[derive(Debug)]
+pub struct Heightmap {
+    map: Matrix<u8>,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+/// The heightmap with the start `S` and the end `E` replaced by their
+/// elevations `a` and `z`.
+pub fn parse_input(input: &str) -> Result<Heightmap, ParseError> {
+    let mut start = None;
+    let mut end = None;
+    let mut width = None;
+    for l in parse::lines(input) {
+        let bytes = l.text().as_bytes();
+        for (col, &b) in bytes.iter().enumerate() {
+            let marker = match b {
+                b'a'..=b'z' => continue,
+                b'S' => &mut start,
+                b'E' => &mut end,
+                _ => return Err(l.error_at(col, "an elevation from `a` to `z`, `S` or `E`")),
+            };
+            if marker.is_some() {
+                return Err(l.error_at(col, "a single `S` and `E`"));
+            }
+            *marker = Some((l.line(), col));
+        }
+        let width = *width.get_or_insert(bytes.len());
+        if bytes.len() != width {
+            return Err(l.error_at(
+                bytes.len().min(width),
+                format!("{width} elevations per line"),
+            ));
+        }
+    }
+    let (Some(start), Some(end)) = (start, end) else {
+        return Err(ParseError::end_of_input(
+            input,
+            "a start `S` and an end `E`",
+        ));
+    };
 
-fn parse_input() -> (Matrix<u8>, (usize, usize), (usize, usize)) {
-    let mut map = Matrix::from_rows(INPUT.lines().map(str::bytes)).unwrap();
-    let start = map.indices().find(|&coord| map[coord] == b'S').unwrap();
-    let end = map.indices().find(|&coord| map[coord] == b'E').unwrap();
+    let mut map = Matrix::from_rows(input.lines().map(str::bytes))
+        .expect("the lines are checked to have the same length");
     map[start] = b'a';
     map[end] = b'z';
-    (map, start, end)
+    Ok(Heightmap { map, start, end })
+}
+
+/// An error at the end `E`, which cannot be reached from `start`.
+fn no_path(heightmap: &Heightmap, start: &str) -> ParseError {
+    let (line, column) = heightmap.end;
+    ParseError::new(
+        line,
+        column,
+        format!("a path from {start} to `E`"),
+        "`E` out of reach",
+    )
 }
 
-pub fn part1() -> usize {
-    let (ref map, start, end) = parse_input();
+pub fn part1(heightmap: &Heightmap) -> Result<usize, ParseError> {
+    let Heightmap { map, start, end } = heightmap;
     bfs(
-        &start,
+        start,
         |&coord| {
             map.neighbours(coord, false)
                 .filter(move |&p| map[p] <= map[coord] + 1)
         },
-        |coord| *coord == end,
+        |coord| coord == end,
     )
     .map(|path| path.len() - 1)
-    .unwrap()
+    .ok_or_else(|| no_path(heightmap, "`S`"))
 }
 
-pub fn part2() -> usize {
-    let (ref map, _, end) = parse_input();
+pub fn part2(heightmap: &Heightmap) -> Result<usize, ParseError> {
+    let Heightmap { map, end, .. } = heightmap;
     // start at the end and find the shortest way to an 'a'
     bfs(
-        &end,
+        end,
         |&coord| {
             map.neighbours(coord, false)
                 .filter(move |&p| map[coord] <= map[p] + 1) // reverse of part 1
@@ -38,34 +94,127 @@ pub fn part2() -> usize {
         |&coord| map[coord] == b'a',
     )
     .map(|path| path.len() - 1)
-    .unwrap()
+    .ok_or_else(|| no_path(heightmap, "an `a`"))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Parsed<'a> = Heightmap;
+
+    const HAS_PARSE_STEP: bool = true;
+
+    fn parse(input: &str) -> Result<Heightmap, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(heightmap: &Heightmap) -> Result<Answer, ParseError> {
+        Ok(part1(heightmap)?.into())
+    }
+
+    fn part2(heightmap: &Heightmap) -> Result<Answer, ParseError> {
+        Ok(part2(heightmap)?.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
 
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi
+";
+
+    #[test]
+    fn test_example() {
+        let heightmap = parse_input(EXAMPLE).unwrap();
+        assert_eq!(part1(&heightmap), Ok(31));
+        assert_eq!(part2(&heightmap), Ok(29));
+    }
+
+    #[test]
+    fn test_no_path() {
+        let heightmap = parse_input("Sabc\nxyzE\n").unwrap();
+        let e = part1(&heightmap).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.found.as_str()),
+            (2, 4, "`E` out of reach")
+        );
+        assert_eq!(e.expected, "a path from `S` to `E`");
+        assert_eq!(
+            part2(&heightmap).unwrap_err().expected,
+            "a path from an `a` to `E`"
+        );
+        // part 2 can start from another `a` than the walled in `S`
+        let input = format!(
+            "S{}\n{}\nabcdefghijklmnopqrstuvwxyE\n",
+            "z".repeat(25),
+            "z".repeat(26)
+        );
+        let heightmap = parse_input(&input).unwrap();
+        assert!(part1(&heightmap).is_err());
+        assert_eq!(part2(&heightmap), Ok(25));
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let heightmap = parse_input(EXAMPLE).unwrap();
+        assert_eq!(heightmap.start, (0, 0));
+        assert_eq!(heightmap.end, (2, 5));
+        // start and end have the elevation of `a` and `z`
+        assert_eq!(heightmap.map[heightmap.start], b'a');
+        assert_eq!(heightmap.map[heightmap.end], b'z');
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_input("Sab\nc1E\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 2, "`1E`"));
+        let e = parse_input("Sab\ncE\n").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (2, 3, "3 elevations per line")
+        );
+        let e = parse_input("SaS\nbcE\n").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 3));
+        assert_eq!(parse_input("Sab\n").unwrap_err().found, "end of input");
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 394);
+        let Some(input) = test_input(12) else { return };
+        assert_eq!(part1(&parse_input(&input).unwrap()), Ok(394));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(12) else { return };
+        b.iter(|| part1(&parse_input(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), 388);
+        let Some(input) = test_input(12) else { return };
+        assert_eq!(part2(&parse_input(&input).unwrap()), Ok(388));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(12) else { return };
+        b.iter(|| part2(&parse_input(&input).unwrap()));
     }
 }
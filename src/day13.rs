@@ -1,34 +1,52 @@
+//! Day 13: Distress Signal
+
 use std::cmp::Ordering;
-use std::cmp::Ordering::{Equal, Greater, Less};
+use std::slice;
 
-const INPUT: &str = include_str!("../inputs/day13.txt");
+use crate::answer::Answer;
+use crate::parse::{self, Cursor, ParseError};
+use crate::solution::Solution;
 
+/// A packet or a part of it.
 #[derive(Debug, PartialEq, Eq)]
-enum Value {
+pub enum Value {
     List(Vec<Value>),
     Number(u8),
 }
 
 impl Value {
-    fn from_str(s: &str) -> Self {
-        if &s[0..1] == "[" {
-            let mut stack: i32 = 0;
-            // pre-allocation helps here
-            let mut vec = Vec::with_capacity(5);
-            s[1..s.len() - 1]
-                .split(|c| {
-                    if c == '[' {
-                        stack += 1
-                    } else if c == ']' {
-                        stack -= 1
-                    }
-                    c == ',' && stack == 0
-                })
-                .filter_map(|s| (!s.is_empty()).then(|| Self::from_str(s)))
-                .for_each(|e| vec.push(e));
-            Self::List(vec)
-        } else {
-            Self::Number(s.parse().unwrap())
+    /// A whole line as a single packet.
+    pub fn from_line(mut line: Cursor<'_>) -> Result<Self, ParseError> {
+        let value = Self::parse(&mut line)?;
+        line.end()?;
+        Ok(value)
+    }
+
+    fn parse(line: &mut Cursor<'_>) -> Result<Self, ParseError> {
+        if line.peek() != Some(b'[') {
+            return line
+                .number()
+                .map(Self::Number)
+                .map_err(|_| line.error("a number or `[`"));
+        }
+
+        line.literal("[")?;
+        // pre-allocation helps here
+        let mut vec = Vec::with_capacity(5);
+        if line.peek() == Some(b']') {
+            line.literal("]")?;
+            return Ok(Self::List(vec));
+        }
+        loop {
+            vec.push(Self::parse(line)?);
+            match line.peek() {
+                Some(b',') => line.literal(",")?,
+                Some(b']') => {
+                    line.literal("]")?;
+                    return Ok(Self::List(vec));
+                }
+                _ => return Err(line.error("`,` or `]`")),
+            }
         }
     }
 }
@@ -37,62 +55,60 @@ impl PartialOrd for Value {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         use Value::*;
 
-        fn first_number(v: &Value) -> Option<u8> {
-            match v {
-                Number(n) => Some(*n),
-                List(l) => {
-                    if l.is_empty() {
-                        None
-                    } else {
-                        first_number(&l[0])
-                    }
-                }
-            }
-        }
         match (self, other) {
             (Number(a), Number(b)) => a.partial_cmp(b),
             (List(a), List(b)) => a.partial_cmp(b),
-            (l @ List(_), r @ Number(_)) => Self::partial_cmp(r, l).map(Ordering::reverse),
-            (Number(n), l @ List(inner)) => {
-                if let Some(other) = first_number(l) {
-                    Some(match n.partial_cmp(&other) {
-                        Some(Equal) if inner.len() > 1 => Less,
-                        Some(other) => other,
-                        None => return None,
-                    })
-                } else {
-                    Some(Greater)
-                }
-            }
+            // a number is compared as a list with only that number
+            (List(a), Number(_)) => a.as_slice().partial_cmp(slice::from_ref(other)),
+            (Number(_), List(b)) => slice::from_ref(self).partial_cmp(b.as_slice()),
         }
     }
 }
 
-pub fn part1() -> usize {
-    INPUT
-        .lines()
-        .filter(|s| !s.is_empty())
-        .map(Value::from_str)
-        .array_chunks::<2>()
+/// Every packet of the input, the empty lines between the pairs are skipped.
+pub fn packets(input: &str) -> impl Iterator<Item = Result<Value, ParseError>> + '_ {
+    parse::lines(input)
+        .filter(|l| !l.is_empty())
+        .map(Value::from_line)
+}
+
+/// The pairs of packets, the second part doesn't care about the pairs but
+/// every packet has a partner nevertheless.
+pub fn parse_pairs(input: &str) -> Result<Vec<(Value, Value)>, ParseError> {
+    let mut packets = packets(input);
+    let mut pairs = Vec::new();
+    while let Some(left) = packets.next() {
+        let left = left?;
+        let right = packets
+            .next()
+            .ok_or_else(|| ParseError::end_of_input(input, "a pair of packets"))?;
+        pairs.push((left, right?));
+    }
+    Ok(pairs)
+}
+
+pub fn part1(pairs: &[(Value, Value)]) -> usize {
+    pairs
+        .iter()
         .enumerate()
-        .filter_map(|(n, p)| (p[0] <= p[1]).then_some(n + 1))
+        .filter(|(_, (left, right))| left <= right)
+        .map(|(i, _)| i + 1)
         .sum()
 }
 
-pub fn part2() -> usize {
+pub fn part2(pairs: &[(Value, Value)]) -> usize {
     use Value::*;
 
     let div1 = List(vec![List(vec![Number(2)])]);
     let div2 = List(vec![List(vec![Number(6)])]);
-    let res = INPUT
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(Value::from_str)
+    let res = pairs
+        .iter()
+        .flat_map(|(left, right)| [left, right])
         .fold((1, 2), |mut acc, v| {
-            if v <= div1 {
+            if *v <= div1 {
                 acc.0 += 1;
             }
-            if v <= div2 {
+            if *v <= div2 {
                 acc.1 += 1;
             }
             acc
@@ -100,31 +116,138 @@ pub fn part2() -> usize {
     res.0 * res.1
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Parsed<'a> = Vec<(Value, Value)>;
+
+    const HAS_PARSE_STEP: bool = true;
+
+    fn parse(input: &str) -> Result<Vec<(Value, Value)>, ParseError> {
+        parse_pairs(input)
+    }
+
+    fn part1(pairs: &Vec<(Value, Value)>) -> Result<Answer, ParseError> {
+        Ok(part1(pairs).into())
+    }
+
+    fn part2(pairs: &Vec<(Value, Value)>) -> Result<Answer, ParseError> {
+        Ok(part2(pairs).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
 
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+    #[test]
+    fn test_example() {
+        let pairs = parse_pairs(EXAMPLE).unwrap();
+        assert_eq!(part1(&pairs), 13);
+        assert_eq!(part2(&pairs), 140);
+    }
+
+    #[test]
+    fn test_value_from_line() {
+        use Value::*;
+
+        let value = |s| Value::from_line(Cursor::new(0, s));
+        assert_eq!(value("[]"), Ok(List(vec![])));
+        assert_eq!(value("[[]]"), Ok(List(vec![List(vec![])])));
+        assert_eq!(
+            value("[10,[2,[]],3]"),
+            Ok(List(vec![
+                Number(10),
+                List(vec![Number(2), List(vec![])]),
+                Number(3)
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_compare_number_with_list() {
+        let value = |s| Value::from_line(Cursor::new(0, s)).unwrap();
+        // 0 is compared with [[0,0]] as [0], which runs out first
+        assert!(value("[0,[10,10]]") < value("[[[0,0]]]"));
+        assert_eq!(part1(&parse_pairs("[0,[10,10]]\n[[[0,0]]]\n").unwrap()), 1);
+        assert_eq!(
+            value("[[1]]").partial_cmp(&value("[1]")),
+            Some(Ordering::Equal)
+        );
+        assert!(value("[1]") < value("[[1],0]"));
+        assert!(value("[[]]") < value("[0]"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_pairs("[1,2]\n[1,,2]\n").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (2, 4, "a number or `[`")
+        );
+        let e = parse_pairs("[1,[2]\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (1, 7, "end of line"));
+        assert_eq!(parse_pairs("[1] 2\n[2]").unwrap_err().found, "` `");
+        assert_eq!(parse_pairs("[1]\n[2]\n\n[3]\n").unwrap_err().line, 5);
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 5588);
+        let Some(input) = test_input(13) else { return };
+        assert_eq!(part1(&parse_pairs(&input).unwrap()), 5588);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(13) else { return };
+        b.iter(|| part1(&parse_pairs(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), 23958);
+        let Some(input) = test_input(13) else { return };
+        assert_eq!(part2(&parse_pairs(&input).unwrap()), 23958);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(13) else { return };
+        b.iter(|| part2(&parse_pairs(&input).unwrap()));
     }
 }
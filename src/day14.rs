@@ -1,12 +1,15 @@
-use itertools::Itertools;
+//! Day 14: Regolith Reservoir
+
 use std::ops::{Add, Index, IndexMut};
 
-const INPUT: &str = include_str!("../inputs/day14.txt");
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
 
-const SAND_SPAWN: Coord = Coord::new(500 - X_BIAS, 0);
-const COLS: usize = 341;
-const ROWS: usize = 171;
-const X_BIAS: usize = 330;
+/// The `x` the sand falls from.
+const SAND_X: usize = 500;
+/// The lowest rock that is supported, the grid grows quadratically with it.
+const MAX_Y: usize = 1000;
 
 /// A normalized coordinate.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -103,7 +106,7 @@ impl Iterator for Interpolate {
 }
 
 fn drop_sand(map: &mut Grid, floor: usize) -> Location {
-    let mut sand = SAND_SPAWN;
+    let mut sand = map.spawn();
     if map[sand] {
         return Location::Spawn;
     }
@@ -139,49 +142,113 @@ fn drop_sand(map: &mut Grid, floor: usize) -> Location {
     }
 }
 
-struct Grid([[bool; COLS]; ROWS]);
+/// The cave, `true` means rock or sand. It only covers the part of the
+/// cave that sand can reach with the floor of part 2, the sand moves at most
+/// one column to the side per row.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    cells: Vec<bool>,
+    cols: usize,
+}
+
+impl Grid {
+    /// An empty cave for rocks down to `max_y`.
+    fn new(max_y: usize) -> Self {
+        // the floor is at `max_y + 2`, the sand stops one row above it
+        let (rows, cols) = (max_y + 2, 2 * (max_y + 2) + 1);
+        Self {
+            cells: vec![false; rows * cols],
+            cols,
+        }
+    }
+
+    /// The `x` of the first column.
+    fn min_x(&self) -> usize {
+        SAND_X.saturating_sub(self.cols / 2)
+    }
+
+    /// The `x` of the last column.
+    fn max_x(&self) -> usize {
+        SAND_X + self.cols / 2
+    }
+
+    /// The normalized coordinate of a point within the columns.
+    fn coord(&self, x: usize, y: usize) -> Coord {
+        Coord::new(x + self.cols / 2 - SAND_X, y)
+    }
+
+    fn spawn(&self) -> Coord {
+        self.coord(SAND_X, 0)
+    }
+
+    /// Adds a horizontal or vertical line of rock, the part of it that the
+    /// sand cannot reach is left out.
+    fn add_rock(&mut self, (x1, y1): (usize, usize), (x2, y2): (usize, usize)) {
+        let from_x = x1.min(x2).max(self.min_x());
+        let to_x = x1.max(x2).min(self.max_x());
+        if from_x <= to_x {
+            let (start, end) = (self.coord(from_x, y1), self.coord(to_x, y2));
+            Interpolate::between(start, end).for_each(|c| self[c] = true);
+        }
+    }
+}
 
 impl Index<Coord> for Grid {
     type Output = bool;
 
     fn index(&self, index: Coord) -> &Self::Output {
-        &self.0[index.row()][index.col()]
+        &self.cells[index.row() * self.cols + index.col()]
     }
 }
 
 impl IndexMut<Coord> for Grid {
     fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
-        &mut self.0[index.row()][index.col()]
+        &mut self.cells[index.row() * self.cols + index.col()]
     }
 }
 
-fn build_sparse_map() -> (Grid, usize) {
-    let mut grid: Grid = Grid([[false; COLS]; ROWS]);
-    let mut max_y = 0_usize;
-
-    INPUT.lines().for_each(|l| {
-        l.split(" -> ")
-            .flat_map(|s| s.split(|c| c == ','))
-            .array_chunks::<2>()
-            .map(|[x, y]| {
-                let x = x.parse::<usize>().unwrap();
-                let y = y.parse::<usize>().unwrap();
-                if y > max_y {
-                    max_y = y;
+/// The rock structures and the lowest `y` of any rock.
+pub fn build_sparse_map(input: &str) -> Result<(Grid, usize), ParseError> {
+    let mut paths = Vec::new();
+    for mut l in parse::lines(input) {
+        let mut path: Vec<(usize, usize)> = Vec::new();
+        loop {
+            let column = l.column();
+            let x = l.number::<usize>()?;
+            l.literal(",")?;
+            let y = l.number::<usize>()?;
+            if y > MAX_Y {
+                let expected = format!("a point with a y of at most {MAX_Y}");
+                return Err(l.error_at(column, expected));
+            }
+            match path.last() {
+                Some(&(px, py)) if px != x && py != y => {
+                    return Err(l.error_at(column, "a point in line with the previous one"));
                 }
-                Coord::new(x - X_BIAS, y)
-            })
-            .tuple_windows::<(Coord, Coord)>()
-            .flat_map(|(l, r)| Interpolate::between(l, r))
-            .for_each(|c| {
-                grid[c] = true;
-            });
-    });
-    (grid, max_y)
+                _ => path.push((x, y)),
+            }
+
+            if l.is_empty() {
+                break;
+            }
+            l.literal(" -> ")?;
+        }
+        paths.push(path);
+    }
+
+    let max_y = paths.iter().flatten().map(|&(_, y)| y).max().unwrap_or(0);
+    let mut grid = Grid::new(max_y);
+    for path in &paths {
+        match path[..] {
+            [point] => grid.add_rock(point, point),
+            _ => path.windows(2).for_each(|w| grid.add_rock(w[0], w[1])),
+        }
+    }
+    Ok((grid, max_y))
 }
 
-pub fn part1() -> usize {
-    let (mut map, max_y) = build_sparse_map();
+pub fn part1((map, max_y): &(Grid, usize)) -> usize {
+    let (mut map, max_y) = (map.clone(), *max_y);
 
     let mut count: usize = 0;
     while matches!(drop_sand(&mut map, max_y + 1), Location::Map) {
@@ -191,8 +258,8 @@ pub fn part1() -> usize {
     count
 }
 
-pub fn part2() -> usize {
-    let (mut map, max_y) = build_sparse_map();
+pub fn part2((map, max_y): &(Grid, usize)) -> usize {
+    let (mut map, max_y) = (map.clone(), *max_y);
     let floor_y = max_y + 2;
 
     let mut count: usize = 0;
@@ -203,14 +270,87 @@ pub fn part2() -> usize {
     count
 }
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Parsed<'a> = (Grid, usize);
+
+    const HAS_PARSE_STEP: bool = true;
+
+    fn parse(input: &str) -> Result<(Grid, usize), ParseError> {
+        build_sparse_map(input)
+    }
+
+    fn part1(map: &(Grid, usize)) -> Result<Answer, ParseError> {
+        Ok(part1(map).into())
+    }
+
+    fn part2(map: &(Grid, usize)) -> Result<Answer, ParseError> {
+        Ok(part2(map).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
 
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9
+";
+
+    #[test]
+    fn test_example() {
+        let map = build_sparse_map(EXAMPLE).unwrap();
+        assert_eq!(part1(&map), 24);
+        assert_eq!(part2(&map), 93);
+    }
+
+    #[test]
+    fn test_build_sparse_map() {
+        let (grid, max_y) = build_sparse_map(EXAMPLE).unwrap();
+        assert_eq!(max_y, 9);
+        assert_eq!((grid.min_x(), grid.max_x()), (489, 511));
+        assert!(grid[grid.coord(498, 5)]);
+        assert!(grid[grid.coord(497, 6)]);
+        assert!(grid[grid.coord(494, 9)]);
+        assert!(!grid[grid.coord(499, 5)]);
+        assert!(!grid[grid.coord(493, 9)]);
+    }
+
+    /// Rocks may be anywhere, the ones the sand cannot reach are left out.
+    #[test]
+    fn test_far_rocks() {
+        let map = build_sparse_map("0,5 -> 1000,5\n").unwrap();
+        assert_eq!((part1(&map), part2(&map)), (25, 25));
+        let map = build_sparse_map("100,3 -> 100,200\n").unwrap();
+        assert_eq!((part1(&map), part2(&map)), (0, 202 * 202));
+        let map = build_sparse_map("500,1000\n").unwrap();
+        assert_eq!(part1(&map), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = build_sparse_map("498,4 -> 498,6\n503,4 - 502,4\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 6, "` -> `"));
+        let e = build_sparse_map("498,4 -> 499,6\n").unwrap_err();
+        assert_eq!((e.column, e.found.as_str()), (10, "`499`"));
+        let e = build_sparse_map("498,4 -> 498,1001\n").unwrap_err();
+        assert_eq!(
+            (e.column, e.expected.as_str()),
+            (10, "a point with a y of at most 1000")
+        );
+        assert_eq!(build_sparse_map("498,4 ->").unwrap_err().column, 6);
+    }
+
     #[test]
     fn test_interpolate() {
         assert_eq!(
@@ -247,21 +387,27 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 843);
+        let Some(input) = test_input(14) else { return };
+        assert_eq!(part1(&build_sparse_map(&input).unwrap()), 843);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(14) else { return };
+        b.iter(|| part1(&build_sparse_map(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), 27625);
+        let Some(input) = test_input(14) else { return };
+        assert_eq!(part2(&build_sparse_map(&input).unwrap()), 27625);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(14) else { return };
+        b.iter(|| part2(&build_sparse_map(&input).unwrap()));
     }
 }
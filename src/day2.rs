@@ -1,13 +1,26 @@
-const INPUT: &str = include_str!("../inputs/day2.txt");
-
-pub fn part1() -> isize {
-    INPUT
-        .lines()
-        .map(|l| {
-            let b = l.as_bytes();
-            (b[0], b[2])
+//! Day 2: Rock Paper Scissors
+
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+/// The shape of the opponent and the second column of every round.
+pub fn parse_rounds(input: &str) -> Result<Vec<(u8, u8)>, ParseError> {
+    parse::lines(input)
+        .map(|mut line| {
+            let left = line.one_of(b"ABC")?;
+            line.literal(" ")?;
+            let right = line.one_of(b"XYZ")?;
+            line.end()?;
+            Ok((left, right))
         })
-        .map(|(left, right)| {
+        .collect()
+}
+
+pub fn part1(rounds: &[(u8, u8)]) -> isize {
+    rounds
+        .iter()
+        .map(|&(left, right)| {
             (match right {
                 b'X' => 1_isize,
                 b'Y' => 2_isize,
@@ -26,14 +39,10 @@ fn winner(left: u8, right: u8) -> isize {
     }
 }
 
-pub fn part2() -> isize {
-    INPUT
-        .lines()
-        .map(|l| {
-            let b = l.as_bytes();
-            (b[0], b[2])
-        })
-        .map(|(left, right)| {
+pub fn part2(rounds: &[(u8, u8)]) -> isize {
+    rounds
+        .iter()
+        .map(|&(left, right)| {
             (match right {
                 b'X' => 0_isize,
                 b'Y' => 3_isize,
@@ -62,30 +71,93 @@ fn interpolate(left: u8, outcome: u8) -> isize {
     }
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Parsed<'a> = Vec<(u8, u8)>;
+
+    const HAS_PARSE_STEP: bool = true;
+
+    fn parse(input: &str) -> Result<Vec<(u8, u8)>, ParseError> {
+        parse_rounds(input)
+    }
+
+    fn part1(rounds: &Vec<(u8, u8)>) -> Result<Answer, ParseError> {
+        Ok(part1(rounds).into())
+    }
+
+    fn part2(rounds: &Vec<(u8, u8)>) -> Result<Answer, ParseError> {
+        Ok(part2(rounds).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "A Y
+B X
+C Z
+";
+
+    #[test]
+    fn test_example() {
+        let rounds = parse_rounds(EXAMPLE).unwrap();
+        assert_eq!(part1(&rounds), 15);
+        assert_eq!(part2(&rounds), 12);
+    }
+
+    #[test]
+    fn test_every_round() {
+        // shape score plus outcome score for every combination
+        assert_eq!(part1(&parse_rounds("A X\nA Y\nA Z").unwrap()), 4 + 8 + 3);
+        assert_eq!(part1(&parse_rounds("B X\nB Y\nB Z").unwrap()), 1 + 5 + 9);
+        assert_eq!(part1(&parse_rounds("C X\nC Y\nC Z").unwrap()), 7 + 2 + 6);
+        assert_eq!(part2(&parse_rounds("A X\nA Y\nA Z").unwrap()), 3 + 4 + 8);
+        assert_eq!(part2(&parse_rounds("B X\nB Y\nB Z").unwrap()), 1 + 5 + 9);
+        assert_eq!(part2(&parse_rounds("C X\nC Y\nC Z").unwrap()), 2 + 6 + 7);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_rounds("A Y\nB  X\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(e.expected, "one of `X`, `Y`, `Z`");
+        assert_eq!(parse_rounds("D X").unwrap_err().found, "`D`");
+        assert_eq!(parse_rounds("A XY").unwrap_err().expected, "end of line");
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 12535);
+        let Some(input) = test_input(2) else { return };
+        assert_eq!(part1(&parse_rounds(&input).unwrap()), 12535);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(2) else { return };
+        b.iter(|| part1(&parse_rounds(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), 15457);
+        let Some(input) = test_input(2) else { return };
+        assert_eq!(part2(&parse_rounds(&input).unwrap()), 15457);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(2) else { return };
+        b.iter(|| part2(&parse_rounds(&input).unwrap()));
     }
 }
@@ -1,11 +1,38 @@
+//! Day 3: Rucksack Reorganization
+
+#[cfg(feature = "nightly")]
 use std::ops::BitAnd;
+#[cfg(feature = "nightly")]
 use std::simd::u8x64;
 
-const INPUT: &str = include_str!("../inputs/day3.txt");
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::{Solution, Variant};
+
+/// Every line is a rucksack with an even number of items, only `a-z` and
+/// `A-Z` are valid items. The elves form groups of three.
+pub fn parse_rucksacks(input: &str) -> Result<Vec<&str>, ParseError> {
+    let rucksacks = parse::lines(input)
+        .map(|line| {
+            let items = line.text();
+            if let Some(column) = items.bytes().position(|b| !b.is_ascii_alphabetic()) {
+                return Err(line.error_at(column, "an item from `a` to `z` or `A` to `Z`"));
+            }
+            if !items.len().is_multiple_of(2) {
+                return Err(line.error_at(items.len(), "an even number of items"));
+            }
+            Ok(items)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if !rucksacks.len().is_multiple_of(3) {
+        return Err(ParseError::end_of_input(input, "three rucksacks per group"));
+    }
+    Ok(rucksacks)
+}
 
-pub fn part1() -> isize {
-    INPUT
-        .lines()
+pub fn part1(rucksacks: &[&str]) -> isize {
+    rucksacks
+        .iter()
         .map(|l| (&l[0..l.len() / 2], &l[l.len() / 2..l.len()]))
         .map(|(left, right)| {
             let mut seen = [false; 52];
@@ -16,7 +43,6 @@ pub fn part1() -> isize {
                 let p = get_index(b);
                 if seen[p as usize] {
                     prio += p as isize + 1; // p + 1 is the priority
-                    seen[p as usize] = false;
                     break;
                 }
             }
@@ -35,12 +61,9 @@ fn get_index(b: u8) -> u8 {
     }
 }
 
-pub fn part2() -> isize {
-    let mut lines = INPUT.lines();
+pub fn part2(rucksacks: &[&str]) -> isize {
     let mut total: isize = 0;
-    while let (Some(line1), Some(line2), Some(line3)) = (lines.next(), lines.next(), lines.next()) {
-        let window = [line1, line2, line3];
-
+    for window in rucksacks.chunks_exact(3) {
         let mut seen = [[false; 52]; 4];
         for i in 0..window.len() {
             window[i]
@@ -63,12 +86,11 @@ pub fn part2() -> isize {
     total
 }
 
-pub fn part2_simd() -> isize {
-    let mut lines = INPUT.lines();
+/// Like [`part2`], but intersects the rucksacks in SIMD lanes.
+#[cfg(feature = "nightly")]
+pub fn part2_simd(rucksacks: &[&str]) -> isize {
     let mut total: isize = 0;
-    while let (Some(line1), Some(line2), Some(line3)) = (lines.next(), lines.next(), lines.next()) {
-        let window = [line1, line2, line3];
-
+    for window in rucksacks.chunks_exact(3) {
         let mut seen = [
             u8x64::from_array([0_u8; 64]),
             u8x64::from_array([0_u8; 64]),
@@ -93,40 +115,140 @@ pub fn part2_simd() -> isize {
     total
 }
 
+/// The stable fallback of the SIMD version, intersects the rucksacks as bit sets.
+#[cfg(not(feature = "nightly"))]
+pub fn part2_simd(rucksacks: &[&str]) -> isize {
+    let mut total: isize = 0;
+    for window in rucksacks.chunks_exact(3) {
+        let mut seen = [0_u64; 3];
+        for i in 0..window.len() {
+            window[i].bytes().for_each(|b| seen[i] |= 1 << get_index(b));
+        }
+
+        let mut res = seen[0] & seen[1] & seen[2];
+        while res != 0 {
+            total += res.trailing_zeros() as isize + 1;
+            res &= res - 1;
+        }
+    }
+
+    total
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Parsed<'a> = Vec<&'a str>;
+
+    const HAS_PARSE_STEP: bool = true;
+
+    fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+        parse_rucksacks(input)
+    }
+
+    fn part1(rucksacks: &Vec<&str>) -> Result<Answer, ParseError> {
+        Ok(part1(rucksacks).into())
+    }
+
+    fn part2(rucksacks: &Vec<&str>) -> Result<Answer, ParseError> {
+        Ok(part2(rucksacks).into())
+    }
+
+    fn variants() -> Vec<Variant<Self>> {
+        vec![Variant {
+            part: 2,
+            name: "part2_simd",
+            run: |rucksacks| Ok(part2_simd(rucksacks).into()),
+        }]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    #[test]
+    fn test_example() {
+        let rucksacks = parse_rucksacks(EXAMPLE).unwrap();
+        assert_eq!(part1(&rucksacks), 157);
+        assert_eq!(part2(&rucksacks), 70);
+        assert_eq!(part2_simd(&rucksacks), 70);
+    }
+
+    #[test]
+    fn test_get_index() {
+        assert_eq!(get_index(b'a'), 0);
+        assert_eq!(get_index(b'z'), 25);
+        assert_eq!(get_index(b'A'), 26);
+        assert_eq!(get_index(b'Z'), 51);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_rucksacks("abAB\nab-B\nab\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 3, "`-`"));
+        let e = parse_rucksacks("ab\nabc\nab\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 4));
+        assert_eq!(e.expected, "an even number of items");
+        let e = parse_rucksacks("ab\nab\nab\nab\n").unwrap_err();
+        assert_eq!(
+            (e.line, e.expected.as_str()),
+            (5, "three rucksacks per group")
+        );
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 8233);
+        let Some(input) = test_input(3) else { return };
+        assert_eq!(part1(&parse_rucksacks(&input).unwrap()), 8233);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(3) else { return };
+        b.iter(|| part1(&parse_rucksacks(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), 2821);
+        let Some(input) = test_input(3) else { return };
+        assert_eq!(part2(&parse_rucksacks(&input).unwrap()), 2821);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(3) else { return };
+        b.iter(|| part2(&parse_rucksacks(&input).unwrap()));
     }
 
     #[test]
     fn test_part2_simd() {
-        assert_eq!(part2_simd(), 2821);
+        let Some(input) = test_input(3) else { return };
+        assert_eq!(part2_simd(&parse_rucksacks(&input).unwrap()), 2821);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2_simd(b: &mut Bencher) {
-        b.iter(part2_simd);
+        let Some(input) = test_input(3) else { return };
+        b.iter(|| part2_simd(&parse_rucksacks(&input).unwrap()));
     }
 }
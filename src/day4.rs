@@ -1,18 +1,32 @@
-const INPUT: &str = include_str!("../inputs/day4.txt");
-
-fn ranges() -> impl Iterator<Item = ((usize, usize), (usize, usize))> {
-    INPUT.lines().map(|l| {
-        let mut split = l.split(|c| c == ',' || c == '-');
-        let left_start = split.next().unwrap().parse::<usize>().unwrap();
-        let left_end = split.next().unwrap().parse::<usize>().unwrap();
-        let right_start = split.next().unwrap().parse::<usize>().unwrap();
-        let right_end = split.next().unwrap().parse::<usize>().unwrap();
-        ((left_start, left_end), (right_start, right_end))
-    })
+//! Day 4: Camp Cleanup
+
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+/// The first and the last section of an assignment.
+pub type Range = (usize, usize);
+
+/// The two assignments of every pair of elves.
+pub fn parse_ranges(input: &str) -> Result<Vec<(Range, Range)>, ParseError> {
+    parse::lines(input)
+        .map(|mut l| {
+            let left_start = l.number()?;
+            l.literal("-")?;
+            let left_end = l.number()?;
+            l.literal(",")?;
+            let right_start = l.number()?;
+            l.literal("-")?;
+            let right_end = l.number()?;
+            l.end()?;
+            Ok(((left_start, left_end), (right_start, right_end)))
+        })
+        .collect()
 }
 
-pub fn part1() -> usize {
-    ranges()
+pub fn part1(ranges: &[(Range, Range)]) -> usize {
+    ranges
+        .iter()
         .filter(|(left, right)| {
             (left.0 <= right.0 && left.1 >= right.0 && left.0 <= right.1 && left.1 >= right.1)
                 || (right.0 <= left.0
@@ -23,8 +37,9 @@ pub fn part1() -> usize {
         .count()
 }
 
-pub fn part2() -> usize {
-    ranges()
+pub fn part2(ranges: &[(Range, Range)]) -> usize {
+    ranges
+        .iter()
         .filter(|(left, right)| {
             left.0 <= right.0 && left.1 >= right.0
                 || left.0 <= right.1 && left.1 >= right.1
@@ -34,31 +49,100 @@ pub fn part2() -> usize {
         .count()
 }
 
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Parsed<'a> = Vec<(Range, Range)>;
+
+    const HAS_PARSE_STEP: bool = true;
+
+    fn parse(input: &str) -> Result<Vec<(Range, Range)>, ParseError> {
+        parse_ranges(input)
+    }
+
+    fn part1(ranges: &Vec<(Range, Range)>) -> Result<Answer, ParseError> {
+        Ok(part1(ranges).into())
+    }
+
+    fn part2(ranges: &Vec<(Range, Range)>) -> Result<Answer, ParseError> {
+        Ok(part2(ranges).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
 
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+    #[test]
+    fn test_example() {
+        let ranges = parse_ranges(EXAMPLE).unwrap();
+        assert_eq!(part1(&ranges), 2);
+        assert_eq!(part2(&ranges), 4);
+    }
+
+    #[test]
+    fn test_parse_ranges() {
+        assert_eq!(
+            parse_ranges("6-6,4-6\n12-345,0-99"),
+            Ok(vec![((6, 6), (4, 6)), ((12, 345), (0, 99))])
+        );
+        // identical ranges contain each other
+        assert_eq!(part1(&[((3, 3), (3, 3))]), 1);
+        assert_eq!(part2(&[((1, 2), (3, 4))]), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_ranges("2-4,6-8\n2-3;4-5\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 4, "`;`"));
+        assert_eq!(e.expected, "`,`");
+        assert_eq!(parse_ranges("2-4,6-").unwrap_err().found, "end of line");
+        assert_eq!(
+            parse_ranges("2-4,6-8,").unwrap_err().expected,
+            "end of line"
+        );
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 528);
+        let Some(input) = test_input(4) else { return };
+        assert_eq!(part1(&parse_ranges(&input).unwrap()), 528);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(4) else { return };
+        b.iter(|| part1(&parse_ranges(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), 881);
+        let Some(input) = test_input(4) else { return };
+        assert_eq!(part2(&parse_ranges(&input).unwrap()), 881);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(4) else { return };
+        b.iter(|| part2(&parse_ranges(&input).unwrap()));
     }
 }
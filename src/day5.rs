@@ -1,73 +1,150 @@
+//! Day 5: Supply Stacks
+
 use std::collections::VecDeque;
 
-const INPUT: &str = include_str!("../inputs/day5.txt");
+use crate::answer::Answer;
+use crate::parse::{self, Cursor, ParseError};
+use crate::solution::Solution;
 
 #[derive(Debug)]
 struct Move {
     count: usize,
     from: usize,
     to: usize,
+    /// The 0-based line of the move, to report impossible moves.
+    line: usize,
 }
 
 impl Move {
-    fn from_line(line: &str) -> Self {
-        let mut split = line.split(|c| c == ' ');
-        let count = split.nth(1).unwrap().parse::<usize>().unwrap();
-        let from = split.nth(1).unwrap().parse::<usize>().unwrap();
-        let to = split.nth(1).unwrap().parse::<usize>().unwrap();
-        Self { count, from, to }
+    fn from_line(mut line: Cursor<'_>, stacks: usize) -> Result<Self, ParseError> {
+        line.literal("move ")?;
+        let count = line.number()?;
+        line.literal(" from ")?;
+        let from = stack_number(&mut line, stacks)?;
+        line.literal(" to ")?;
+        let to = stack_number(&mut line, stacks)?;
+        line.end()?;
+        Ok(Self {
+            count,
+            from,
+            to,
+            line: line.line(),
+        })
+    }
+
+    /// Checks that the stack to take the crates from has enough crates.
+    fn check(&self, stacks: &[VecDeque<u8>]) -> Result<(), ParseError> {
+        let available = stacks[self.from - 1].len();
+        if self.count > available {
+            return Err(ParseError::new(
+                self.line,
+                "move ".len(),
+                format!("at most {available} crates"),
+                format!("`{}`", self.count),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn stack_number(line: &mut Cursor<'_>, stacks: usize) -> Result<usize, ParseError> {
+    let column = line.column();
+    let n = line.number()?;
+    if !(1..=stacks).contains(&n) {
+        return Err(line.error_at(column, format!("a stack from 1 to {stacks}")));
     }
+    Ok(n)
 }
 
-pub fn part1() -> String {
-    let mut stacks: Vec<VecDeque<u8>> = vec![VecDeque::new(); 9];
-    INPUT.lines().take(8).for_each(|l| {
-        let mut elements = [None; 9];
-        for (i, p) in (1..l.len()).step_by(4).enumerate() {
-            let c = l.as_bytes()[p];
-            elements[i] = if c == b' ' { None } else { Some(c) };
+/// Parses the drawing of the stacks, the top crate is at the front.
+/// Returns the stacks and the remaining lines, which are the moves.
+///
+/// The last line of the drawing numbers the stacks, every stack takes
+/// four columns.
+pub fn parse_stacks(
+    input: &str,
+) -> Result<(Vec<VecDeque<u8>>, impl Iterator<Item = Cursor<'_>>), ParseError> {
+    let mut lines = parse::lines(input);
+    let drawing = lines
+        .by_ref()
+        .take_while(|l| !l.is_empty())
+        .collect::<Vec<_>>();
+    let Some((numbers, crates)) = drawing.split_last() else {
+        return Err(ParseError::end_of_input(input, "a drawing of the stacks"));
+    };
+    let mut numbers = numbers.clone();
+    let mut count = 0;
+    while !numbers.is_empty() {
+        numbers.literal(" ")?;
+        let column = numbers.column();
+        let n: usize = numbers.number()?;
+        count += 1;
+        if n != count {
+            return Err(numbers.error_at(column, format!("stack {count}")));
         }
-        for (i, e) in elements
-            .into_iter()
-            .enumerate()
-            .filter(|(_, e)| e.is_some())
-        {
-            stacks[i].push_back(e.unwrap());
+        // stacks are separated by three spaces, the last one may be followed by one
+        let _ = numbers.literal("  ").or_else(|_| numbers.literal(" "));
+    }
+
+    let mut stacks = vec![VecDeque::new(); count];
+    for l in crates.iter().rev() {
+        let bytes = l.text().as_bytes();
+        for (i, p) in (1..bytes.len()).step_by(4).enumerate() {
+            let c = bytes[p];
+            if c == b' ' {
+                continue;
+            }
+            if i >= count || bytes[p - 1] != b'[' || bytes.get(p + 1) != Some(&b']') {
+                return Err(l.error_at(p - 1, format!("a crate like `[A]` on stacks 1 to {count}")));
+            }
+            stacks[i].push_front(c);
         }
-    });
+    }
+    Ok((stacks, lines))
+}
+
+/// The crates on top of every stack, empty stacks are a space.
+fn top_crates(stacks: &[VecDeque<u8>]) -> String {
+    stacks
+        .iter()
+        .map(|s| s.front().map_or(' ', |&c| c as char))
+        .collect()
+}
+
+/// The stacks of crates and the moves of the crane.
+#[derive(Debug)]
+pub struct Procedure {
+    stacks: Vec<VecDeque<u8>>,
+    moves: Vec<Move>,
+}
+
+pub fn parse_procedure(input: &str) -> Result<Procedure, ParseError> {
+    let (stacks, moves) = parse_stacks(input)?;
+    let moves = moves
+        .map(|line| Move::from_line(line, stacks.len()))
+        .collect::<Result<_, _>>()?;
+    Ok(Procedure { stacks, moves })
+}
 
-    INPUT.lines().skip(10).map(Move::from_line).for_each(|m| {
+pub fn part1(procedure: &Procedure) -> Result<String, ParseError> {
+    let mut stacks = procedure.stacks.clone();
+
+    for m in &procedure.moves {
+        m.check(&stacks)?;
         for _ in 0..m.count {
             let elem = stacks[m.from - 1].pop_front().unwrap();
             stacks[m.to - 1].push_front(elem);
         }
-    });
+    }
 
-    let mut res = String::with_capacity(stacks.len());
-    stacks
-        .iter()
-        .for_each(|s| res.push(*s.front().unwrap() as char));
-    res
+    Ok(top_crates(&stacks))
 }
 
-pub fn part2() -> String {
-    let mut stacks: Vec<VecDeque<u8>> = vec![VecDeque::new(); 9];
-    INPUT.lines().take(8).for_each(|l| {
-        let mut elements = [None; 9];
-        for (i, p) in (1..l.len()).step_by(4).enumerate() {
-            let c = l.as_bytes()[p];
-            elements[i] = if c == b' ' { None } else { Some(c) };
-        }
-        for (i, e) in elements
-            .into_iter()
-            .enumerate()
-            .filter(|(_, e)| e.is_some())
-        {
-            stacks[i].push_back(e.unwrap());
-        }
-    });
+pub fn part2(procedure: &Procedure) -> Result<String, ParseError> {
+    let mut stacks = procedure.stacks.clone();
 
-    INPUT.lines().skip(10).map(Move::from_line).for_each(|m| {
+    for m in &procedure.moves {
+        m.check(&stacks)?;
         let mut elems: VecDeque<u8> = VecDeque::with_capacity(m.count);
         for _ in 0..m.count {
             let elem = stacks[m.from - 1].pop_front().unwrap();
@@ -76,39 +153,126 @@ pub fn part2() -> String {
         elems
             .into_iter()
             .for_each(|c| stacks[m.to - 1].push_front(c));
-    });
+    }
 
-    let mut res = String::with_capacity(stacks.len());
-    stacks
-        .iter()
-        .for_each(|s| res.push(*s.front().unwrap() as char));
-    res
+    Ok(top_crates(&stacks))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Parsed<'a> = Procedure;
+
+    const HAS_PARSE_STEP: bool = true;
+
+    fn parse(input: &str) -> Result<Procedure, ParseError> {
+        parse_procedure(input)
+    }
+
+    fn part1(procedure: &Procedure) -> Result<Answer, ParseError> {
+        part1(procedure).map(Answer::from)
+    }
+
+    fn part2(procedure: &Procedure) -> Result<Answer, ParseError> {
+        part2(procedure).map(Answer::from)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+    #[test]
+    fn test_example() {
+        let procedure = parse_procedure(EXAMPLE).unwrap();
+        assert_eq!(part1(&procedure).as_deref(), Ok("CMZ"));
+        assert_eq!(part2(&procedure).as_deref(), Ok("MCD"));
+    }
+
+    #[test]
+    fn test_parse_stacks() {
+        let (stacks, mut moves) = parse_stacks(EXAMPLE).unwrap();
+        assert_eq!(
+            stacks,
+            vec![
+                VecDeque::from(*b"NZ"),
+                VecDeque::from(*b"DCM"),
+                VecDeque::from(*b"P")
+            ]
+        );
+        assert_eq!(moves.next().map(|m| m.line()), Some(5));
+        // editors like to strip the trailing spaces of the drawing
+        let (stacks, _) = parse_stacks("    [D]\n[N] [C]\n 1   2   3\n").unwrap();
+        assert_eq!(stacks.len(), 3);
+    }
+
+    #[test]
+    fn test_move_from_line() {
+        let m = Move::from_line(Cursor::new(0, "move 12 from 3 to 10"), 10).unwrap();
+        assert_eq!((m.count, m.from, m.to), (12, 3, 10));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_procedure("[A]\n 1   2\n\nmove 1 from 1 to 3\n").unwrap_err();
+        assert_eq!((e.line, e.column), (4, 18));
+        assert_eq!(e.expected, "a stack from 1 to 2");
+        let procedure = parse_procedure("[A]\n 1\n\nmove 2 from 1 to 1\n").unwrap();
+        let e = part2(&procedure).unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (4, 6, "`2`"));
+        let e = parse_procedure("[A] (B)\n 1   2\n\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (1, 5, "`(`"));
+        assert_eq!(
+            parse_procedure(" 1   3\n\n").unwrap_err().expected,
+            "stack 2"
+        );
+        assert_eq!(parse_procedure("").unwrap_err().found, "end of input");
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), "SBPQRSCDF");
+        let Some(input) = test_input(5) else { return };
+        let procedure = parse_procedure(&input).unwrap();
+        assert_eq!(part1(&procedure).as_deref(), Ok("SBPQRSCDF"));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(5) else { return };
+        b.iter(|| part1(&parse_procedure(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), "RGLVRCQSB");
+        let Some(input) = test_input(5) else { return };
+        let procedure = parse_procedure(&input).unwrap();
+        assert_eq!(part2(&procedure).as_deref(), Ok("RGLVRCQSB"));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(5) else { return };
+        b.iter(|| part2(&parse_procedure(&input).unwrap()));
     }
 }
@@ -1,11 +1,38 @@
-const INPUT: &str = include_str!("../inputs/day6.txt");
+//! Day 6: Tuning Trouble
 
-pub fn part1() -> usize {
+use crate::answer::Answer;
+use crate::parse::{self, Cursor, ParseError};
+use crate::solution::Solution;
+
+/// The datastream is a single line of `a` to `z`.
+pub fn datastream(input: &str) -> Result<Cursor<'_>, ParseError> {
+    let mut lines = parse::lines(input);
+    let line = lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, "a datastream"))?;
+    if let Some(column) = line.text().bytes().position(|b| !b.is_ascii_lowercase()) {
+        return Err(line.error_at(column, "a letter from `a` to `z`"));
+    }
+    if let Some(line) = lines.next() {
+        return Err(line.error("end of input"));
+    }
+    Ok(line)
+}
+
+fn no_marker(stream: &Cursor<'_>, window_size: usize) -> ParseError {
+    stream.error_at(
+        stream.text().len(),
+        format!("a marker of {window_size} different letters"),
+    )
+}
+
+pub fn part1(stream: &Cursor<'_>) -> Result<usize, ParseError> {
     // Although both parts could be solved with a bool array for the `seen` letters,
     // this approach for part 1 only requires 65% of the time.
 
     const WINDOW_SIZE: usize = 4;
-    INPUT
+    stream
+        .text()
         .as_bytes()
         .windows(WINDOW_SIZE)
         .position(|s| {
@@ -13,20 +40,21 @@ pub fn part1() -> usize {
                 && (s[1] != s[2] && s[1] != s[3])
                 && (s[2] != s[3])
         })
-        .unwrap()
-        + WINDOW_SIZE
+        .map(|p| p + WINDOW_SIZE)
+        .ok_or_else(|| no_marker(stream, WINDOW_SIZE))
 }
 
-pub fn part2() -> usize {
+pub fn part2(stream: &Cursor<'_>) -> Result<usize, ParseError> {
     // Unlike for part 1, using explicit comparisons here makes the whole thing
     // a lot slower (by factors).
 
     const WINDOW_SIZE: usize = 14;
-    INPUT
+    stream
+        .text()
         .as_bytes()
         .windows(WINDOW_SIZE)
         .position(|s| {
-            let mut seen = [false; 26]; // let's hope it's only a-z
+            let mut seen = [false; 26]; // `datastream` checked that it's only a-z
             for &e in s {
                 let c = (e - b'a') as usize;
                 if seen[c] {
@@ -36,35 +64,106 @@ pub fn part2() -> usize {
             }
             true
         })
-        .unwrap()
-        + WINDOW_SIZE
+        .map(|p| p + WINDOW_SIZE)
+        .ok_or_else(|| no_marker(stream, WINDOW_SIZE))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Parsed<'a> = Cursor<'a>;
+
+    const HAS_PARSE_STEP: bool = true;
+
+    fn parse(input: &str) -> Result<Cursor<'_>, ParseError> {
+        datastream(input)
+    }
+
+    fn part1(stream: &Cursor<'_>) -> Result<Answer, ParseError> {
+        part1(stream).map(Answer::from)
+    }
+
+    fn part2(stream: &Cursor<'_>) -> Result<Answer, ParseError> {
+        part2(stream).map(Answer::from)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
 
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb
+";
+
+    #[test]
+    fn test_example() {
+        assert_eq!(part1(&datastream(EXAMPLE).unwrap()), Ok(7));
+        assert_eq!(part2(&datastream(EXAMPLE).unwrap()), Ok(19));
+    }
+
+    #[test]
+    fn test_more_examples() {
+        let packet = |s| part1(&datastream(s).unwrap());
+        let message = |s| part2(&datastream(s).unwrap());
+        assert_eq!(packet("bvwbjplbgvbhsrlpgdmjqwftvncz"), Ok(5));
+        assert_eq!(packet("nppdvjthqldpwncqszvftbrmjlhg"), Ok(6));
+        assert_eq!(packet("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Ok(10));
+        assert_eq!(packet("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Ok(11));
+        assert_eq!(message("bvwbjplbgvbhsrlpgdmjqwftvncz"), Ok(23));
+        assert_eq!(message("nppdvjthqldpwncqszvftbrmjlhg"), Ok(23));
+        assert_eq!(message("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Ok(29));
+        assert_eq!(message("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Ok(26));
+    }
+
+    #[test]
+    fn test_marker_at_the_start() {
+        assert_eq!(part1(&datastream("abcd").unwrap()), Ok(4));
+        assert_eq!(part2(&datastream("abcdefghijklmn").unwrap()), Ok(14));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = datastream("abcD").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (1, 4, "`D`"));
+        let e = part1(&datastream("abcabc\n").unwrap()).unwrap_err();
+        assert_eq!((e.line, e.column), (1, 7));
+        assert_eq!(e.expected, "a marker of 4 different letters");
+        assert_eq!(datastream("abcd\nefgh").unwrap_err().line, 2);
+        assert_eq!(datastream("").unwrap_err().found, "end of input");
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 1702);
+        let Some(input) = test_input(6) else { return };
+        assert_eq!(part1(&datastream(&input).unwrap()), Ok(1702));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(6) else { return };
+        b.iter(|| part1(&datastream(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), 3559);
+        let Some(input) = test_input(6) else { return };
+        assert_eq!(part2(&datastream(&input).unwrap()), Ok(3559));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(6) else { return };
+        b.iter(|| part2(&datastream(&input).unwrap()));
     }
 }
@@ -1,80 +1,205 @@
+//! Day 7: No Space Left On Device
+
 use std::collections::HashMap;
 
-const INPUT: &str = include_str!("../inputs/day7.txt");
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
 
 // TODO: the tree iteration is a depth first search, so this may be a thing to optimize for
 
-fn compute_sizes() -> HashMap<Vec<&'static str>, usize> {
+/// The total size of every directory, keyed by its path.
+pub type Sizes<'a> = HashMap<Vec<&'a str>, usize>;
+
+/// Replays the terminal output and sums up the file sizes of every
+/// directory, including its subdirectories.
+pub fn compute_sizes(input: &str) -> Result<Sizes<'_>, ParseError> {
     let mut sizes: HashMap<Vec<&str>, usize> = HashMap::new();
     let mut cwd: Vec<&str> = Vec::new();
-    INPUT.lines().for_each(|l| {
-        let bytes = l.as_bytes();
-        match bytes[0] {
-            b'$' => {
-                if bytes[2] == b'c' {
-                    match &bytes[5] {
-                        b'.' => {
-                            cwd.pop().unwrap();
+    for mut l in parse::lines(input) {
+        match l.peek() {
+            Some(b'$') => {
+                l.literal("$ ")?;
+                if l.literal("cd ").is_ok() {
+                    match l.rest() {
+                        "" => return Err(l.error("a directory")),
+                        ".." => {
+                            if cwd.pop().is_none() {
+                                return Err(l.error_at(5, "a directory below the root"));
+                            }
                         }
-                        _ => {
-                            cwd.push(&l[5..]);
+                        dir => {
+                            cwd.push(dir);
                             // TODO: the hash map could maybe hold a &Vec instead of a Vec, which would allow us, to store references instead of cloning
                             sizes.insert(cwd.clone(), 0); // TODO: get rid of the clone
                         }
                     }
+                } else {
+                    l.literal("ls")?;
+                    l.end()?;
                 }
             }
-            b'd' => {} // dir
-            _ => {
-                let size = l.split(' ').next().unwrap().parse::<usize>().unwrap();
+            Some(b'd') => l.literal("dir ")?,
+            Some(b'0'..=b'9') => {
+                let size = l.number::<usize>()?;
+                l.literal(" ")?;
+                if cwd.is_empty() {
+                    return Err(l.error_at(0, "`$ cd` before the first file"));
+                }
 
                 for i in (1..cwd.len() + 1).rev() {
                     *sizes.get_mut(&cwd[0..i]).unwrap() += size;
                 }
             }
+            _ => return Err(l.error("a command, a directory or a file")),
         }
-    });
-    sizes
+    }
+    if !sizes.contains_key(&vec!["/"]) {
+        return Err(ParseError::end_of_input(input, "`$ cd /`"));
+    }
+    Ok(sizes)
 }
 
-pub fn part1() -> usize {
-    let sizes = compute_sizes();
+pub fn part1(sizes: &Sizes) -> usize {
     sizes.values().filter(|&&v| v <= 100000).sum::<usize>()
 }
 
-pub fn part2() -> usize {
-    let sizes = compute_sizes();
+pub fn part2(sizes: &Sizes) -> usize {
     let root_size = sizes.get(&vec!["/"]).unwrap();
     let needed = 70000000 - 30000000;
-    let threshold = root_size - needed;
+    // with enough free space the smallest directory, if any, is deleted
+    let threshold = root_size.saturating_sub(needed);
     *sizes.values().filter(|&&v| v >= threshold).min().unwrap()
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Parsed<'a> = Sizes<'a>;
+
+    const HAS_PARSE_STEP: bool = true;
+
+    fn parse(input: &str) -> Result<Sizes<'_>, ParseError> {
+        compute_sizes(input)
+    }
+
+    fn part1(sizes: &Sizes) -> Result<Answer, ParseError> {
+        Ok(part1(sizes).into())
+    }
+
+    fn part2(sizes: &Sizes) -> Result<Answer, ParseError> {
+        Ok(part2(sizes).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
 
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+    #[test]
+    fn test_example() {
+        let sizes = compute_sizes(EXAMPLE).unwrap();
+        assert_eq!(part1(&sizes), 95437);
+        assert_eq!(part2(&sizes), 24933642);
+    }
+
+    /// A disk with more than 30,000,000 free already.
+    #[test]
+    fn test_small_disk() {
+        let sizes = compute_sizes("$ cd /\n$ ls\n100 a\n").unwrap();
+        assert_eq!(part2(&sizes), 100);
+        let sizes = compute_sizes("$ cd /\n$ ls\n200 a\n$ cd b\n$ ls\n300 c\n").unwrap();
+        assert_eq!(part2(&sizes), 300);
+    }
+
+    #[test]
+    fn test_compute_sizes() {
+        let sizes = compute_sizes(EXAMPLE).unwrap();
+        assert_eq!(sizes.len(), 4);
+        assert_eq!(sizes[&vec!["/", "a", "e"]], 584);
+        assert_eq!(sizes[&vec!["/", "a"]], 94853);
+        assert_eq!(sizes[&vec!["/", "d"]], 24933642);
+        assert_eq!(sizes[&vec!["/"]], 48381165);
+    }
+
+    #[test]
+    fn test_same_name_in_different_directories() {
+        let sizes = compute_sizes("$ cd /\n$ cd a\n1 f\n$ cd ..\n$ cd b\n$ cd a\n2 f\n").unwrap();
+        assert_eq!(sizes[&vec!["/", "a"]], 1);
+        assert_eq!(sizes[&vec!["/", "b", "a"]], 2);
+        assert_eq!(sizes[&vec!["/"]], 3);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = compute_sizes("$ cd /\n$ cd ..\n$ cd ..\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (3, 6, "`.`"));
+        let e = compute_sizes("$ cd /\n$ dir\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 3, "`ls`"));
+        let e = compute_sizes("$ cd /\n12a b\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(compute_sizes("$ cd /\nfile\n").unwrap_err().found, "`file`");
+        assert_eq!(compute_sizes("1 a\n").unwrap_err().line, 1);
+        assert_eq!(compute_sizes("").unwrap_err().expected, "`$ cd /`");
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 1583951);
+        let Some(input) = test_input(7) else { return };
+        assert_eq!(part1(&compute_sizes(&input).unwrap()), 1583951);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(7) else { return };
+        b.iter(|| part1(&compute_sizes(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), 214171);
+        let Some(input) = test_input(7) else { return };
+        assert_eq!(part2(&compute_sizes(&input).unwrap()), 214171);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(7) else { return };
+        b.iter(|| part2(&compute_sizes(&input).unwrap()));
     }
 }
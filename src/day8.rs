@@ -1,15 +1,16 @@
-use std::ops::Deref;
+//! Day 8: Treetop Tree House
 
-const INPUT: &str = include_str!("../inputs/day8.txt");
+use std::ops::Deref;
 
-const LINES: usize = 99;
-const COLS: usize = 99;
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
-struct Coordinate(u8, u8);
+struct Coordinate(usize, usize);
 
 impl Coordinate {
-    fn new(line: u8, col: u8) -> Self {
+    fn new(line: usize, col: usize) -> Self {
         Self(line, col)
     }
 
@@ -18,26 +19,35 @@ impl Coordinate {
     }
 
     fn line(&self) -> usize {
-        self.0 as usize
+        self.0
     }
 
     fn col(&self) -> usize {
-        self.1 as usize
+        self.1
     }
 }
 
+/// The tree heights, the grid is rectangular but may have any size.
 #[derive(Debug)]
-struct Grid([[u8; COLS]; LINES]);
+pub struct Grid(Vec<Vec<u8>>);
 
-impl From<[[u8; COLS]; LINES]> for Grid {
-    fn from(v: [[u8; COLS]; LINES]) -> Self {
+impl From<Vec<Vec<u8>>> for Grid {
+    fn from(v: Vec<Vec<u8>>) -> Self {
         Self(v)
     }
 }
 
 impl Grid {
+    fn line_count(&self) -> usize {
+        self.0.len()
+    }
+
+    fn col_count(&self) -> usize {
+        self.0.first().map_or(0, Vec::len)
+    }
+
     fn transpose(&self) -> Self {
-        let mut rotated = Self::from([[0_u8; COLS]; LINES]);
+        let mut rotated = Self::from(vec![vec![0_u8; self.line_count()]; self.col_count()]);
 
         for (row, line) in self.0.iter().enumerate() {
             for (col, c) in line.iter().enumerate() {
@@ -52,7 +62,7 @@ impl Grid {
         self.lines().enumerate().flat_map(|(line_num, line)| {
             line.iter()
                 .enumerate()
-                .map(move |(col_num, _)| Coordinate::new(line_num as u8, col_num as u8))
+                .map(move |(col_num, _)| Coordinate::new(line_num, col_num))
         })
     }
 
@@ -60,80 +70,103 @@ impl Grid {
         self.line(coord.line()).0[coord.col()]
     }
 
-    fn line(&self, line: usize) -> Line {
+    fn line(&self, line: usize) -> Line<'_> {
         Line(&self.0[line])
     }
 
-    fn lines(&self) -> impl Iterator<Item = Line> {
-        self.0.iter().map(Line)
+    fn lines(&self) -> impl Iterator<Item = Line<'_>> {
+        self.0.iter().map(|l| Line(l))
     }
 }
 
 #[derive(Debug)]
-struct Line<'a>(&'a [u8; COLS]);
+struct Line<'a>(&'a [u8]);
 
 impl<'a> Deref for Line<'a> {
-    type Target = &'a [u8; COLS];
+    type Target = &'a [u8];
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-fn get_grid() -> Grid {
-    let mut grid = [[0_u8; COLS]; LINES];
-    INPUT.lines().enumerate().for_each(|(line_num, l)| {
-        l.bytes()
-            .map(|b| b - b'0')
-            .enumerate()
-            .for_each(|(col_num, c)| {
-                grid[line_num][col_num] = c;
-            })
-    });
-    Grid::from(grid)
+/// The tree heights, every line has to have the same length.
+pub fn get_grid(input: &str) -> Result<Grid, ParseError> {
+    let mut grid: Vec<Vec<u8>> = Vec::new();
+    for l in parse::lines(input) {
+        let bytes = l.text().as_bytes();
+        if let Some(column) = bytes.iter().position(|b| !b.is_ascii_digit()) {
+            return Err(l.error_at(column, "a tree height from `0` to `9`"));
+        }
+        if let Some(first) = grid.first() {
+            if bytes.len() != first.len() {
+                let expected = format!("{} trees per line", first.len());
+                return Err(l.error_at(bytes.len().min(first.len()), expected));
+            }
+        }
+        grid.push(bytes.iter().map(|b| b - b'0').collect());
+    }
+    if grid.is_empty() || grid[0].is_empty() {
+        return Err(ParseError::end_of_input(
+            input,
+            "a tree height from `0` to `9`",
+        ));
+    }
+    Ok(Grid::from(grid))
 }
 
 fn get_visible(grid: &Grid) -> impl Iterator<Item = Coordinate> + '_ {
     grid.lines()
         .enumerate()
-        .take(LINES - 1)
+        .take(grid.line_count().saturating_sub(1))
         .skip(1)
         .flat_map(|(line_num, line)| {
             let mut highest_left = line[0];
             let mut highest_right = line[line.len() - 1];
+            // the trees on the edge are always visible and counted separately
+            let inner = &line[..line.len() - 1];
             let visible = Iterator::chain(
-                line.iter().enumerate().filter_map(|(col_num, &c)| {
-                    if c > highest_left {
-                        highest_left = c;
-                        Some(Coordinate::new(line_num as u8, col_num as u8))
-                    } else {
-                        None
-                    }
-                }),
-                line.iter().enumerate().rev().filter_map(|(col_num, &c)| {
-                    if c > highest_right {
-                        highest_right = c;
-                        Some(Coordinate::new(line_num as u8, col_num as u8))
-                    } else {
-                        None
-                    }
-                }),
+                inner
+                    .iter()
+                    .enumerate()
+                    .skip(1)
+                    .filter_map(|(col_num, &c)| {
+                        if c > highest_left {
+                            highest_left = c;
+                            Some(Coordinate::new(line_num, col_num))
+                        } else {
+                            None
+                        }
+                    }),
+                inner
+                    .iter()
+                    .enumerate()
+                    .skip(1)
+                    .rev()
+                    .filter_map(|(col_num, &c)| {
+                        if c > highest_right {
+                            highest_right = c;
+                            Some(Coordinate::new(line_num, col_num))
+                        } else {
+                            None
+                        }
+                    }),
             )
             .collect::<Vec<Coordinate>>(); // TODO: remove this collect
             visible.into_iter()
         })
 }
 
-pub fn part1() -> usize {
-    const VISIBLE_FROM_EDGE: usize = (COLS + LINES) * 2 - 4;
-    let grid = get_grid();
-    let mut visible = get_visible(&grid)
+pub fn part1(grid: &Grid) -> usize {
+    let (lines, cols) = (grid.line_count(), grid.col_count());
+    let visible_from_edge = lines * cols - lines.saturating_sub(2) * cols.saturating_sub(2);
+    let mut visible = get_visible(grid)
         .chain(get_visible(&grid.transpose()).map(|c| c.transpose()))
         .collect::<Vec<Coordinate>>();
     visible.sort_unstable();
     visible.dedup();
 
-    visible.len() + VISIBLE_FROM_EDGE
+    visible.len() + visible_from_edge
 }
 
 fn scenic_score_row(g: &Grid, loc: Coordinate) -> usize {
@@ -147,7 +180,7 @@ fn scenic_score_row(g: &Grid, loc: Coordinate) -> usize {
         }
     }
     let mut score_left: usize = 0;
-    for &c in line.iter().rev().skip(COLS - loc.col()) {
+    for &c in line.iter().rev().skip(line.len() - loc.col()) {
         score_left += 1;
         if c >= height {
             break;
@@ -156,42 +189,130 @@ fn scenic_score_row(g: &Grid, loc: Coordinate) -> usize {
     score_right * score_left
 }
 
-pub fn part2() -> usize {
-    let grid = get_grid();
+pub fn part2(grid: &Grid) -> usize {
     let grid_transp = grid.transpose();
     let scenic_score = |loc: Coordinate| -> usize {
-        let row = scenic_score_row(&grid, loc);
+        let row = scenic_score_row(grid, loc);
         let col = scenic_score_row(&grid_transp, loc.transpose());
         row * col
     };
     grid.coordinates().map(scenic_score).max().unwrap()
 }
 
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Parsed<'a> = Grid;
+
+    const HAS_PARSE_STEP: bool = true;
+
+    fn parse(input: &str) -> Result<Grid, ParseError> {
+        get_grid(input)
+    }
+
+    fn part1(grid: &Grid) -> Result<Answer, ParseError> {
+        Ok(part1(grid).into())
+    }
+
+    fn part2(grid: &Grid) -> Result<Answer, ParseError> {
+        Ok(part2(grid).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
 
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "30373
+25512
+65332
+33549
+35390
+";
+
+    #[test]
+    fn test_example() {
+        let grid = get_grid(EXAMPLE).unwrap();
+        assert_eq!(part1(&grid), 21);
+        assert_eq!(part2(&grid), 8);
+    }
+
+    #[test]
+    fn test_get_grid() {
+        let grid = get_grid("012\n345\n").unwrap();
+        assert_eq!((grid.line_count(), grid.col_count()), (2, 3));
+        assert_eq!(grid.height_at(Coordinate::new(1, 2)), 5);
+        assert_eq!(grid.transpose().height_at(Coordinate::new(2, 1)), 5);
+    }
+
+    #[test]
+    fn test_only_edges() {
+        assert_eq!(part1(&get_grid("5").unwrap()), 1);
+        assert_eq!(part1(&get_grid("12\n34").unwrap()), 4);
+        assert_eq!(part1(&get_grid("123").unwrap()), 3);
+    }
+
+    /// Grids may be larger than a real input, with more than 256 lines or
+    /// columns.
+    #[test]
+    fn test_large_grid() {
+        // the middle tree of every line is visible from the left
+        let grid = get_grid(&"051\n".repeat(300)).unwrap();
+        assert_eq!(part1(&grid), 900);
+        assert_eq!(part1(&grid.transpose()), 900);
+
+        let mut middle = vec![b'0'; 300];
+        middle[280] = b'9';
+        let middle = String::from_utf8(middle).unwrap();
+        let zeros = "0".repeat(300);
+        let grid = get_grid(&format!("{zeros}\n{middle}\n{zeros}\n")).unwrap();
+        assert_eq!(part2(&grid), 280 * 19);
+        assert_eq!(part2(&grid.transpose()), 280 * 19);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = get_grid("123\n4a6\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 2, "`a6`"));
+        let e = get_grid("123\n45\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 3, "end of line"));
+        assert_eq!(e.expected, "3 trees per line");
+        assert_eq!(get_grid("123\n4567\n").unwrap_err().found, "`7`");
+        assert_eq!(get_grid("").unwrap_err().found, "end of input");
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 1827);
+        let Some(input) = test_input(8) else { return };
+        assert_eq!(part1(&get_grid(&input).unwrap()), 1827);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(8) else { return };
+        b.iter(|| part1(&get_grid(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), 335580);
+        let Some(input) = test_input(8) else { return };
+        assert_eq!(part2(&get_grid(&input).unwrap()), 335580);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(8) else { return };
+        b.iter(|| part2(&get_grid(&input).unwrap()));
     }
 }
@@ -1,6 +1,12 @@
-const INPUT: &str = include_str!("../inputs/day9.txt");
+//! Day 9: Rope Bridge
 
-#[derive(Debug, Clone)]
+use std::collections::HashSet;
+
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct Coord(isize, isize);
 
 impl Coord {
@@ -31,20 +37,102 @@ impl Coord {
     }
 }
 
-fn parse_input() -> impl Iterator<Item = (u8, isize)> {
-    INPUT
-        .lines()
-        .map(|l| (l.as_bytes()[0], &l[2..]))
-        .map(|(c, n)| (c, n.parse::<isize>().unwrap()))
+/// The largest area of a grid of visited positions, 16 MiB. Motions that
+/// cover more are tracked in a set instead.
+const MAX_GRID_AREA: usize = 1 << 24;
+
+/// The direction and the number of steps of every motion, and the area the
+/// head moves in. The other knots follow the head, so they stay in it too.
+#[derive(Debug)]
+pub struct Motions {
+    motions: Vec<(u8, isize)>,
+    min: Coord,
+    max: Coord,
 }
 
-pub fn part1() -> usize {
-    const NORMALIZE_BIAS: isize = 112; // magic number
-    let mut seen_grid = [[false; 256]; 256]; // 64KiB "only"
+pub fn parse_motions(input: &str) -> Result<Motions, ParseError> {
+    let mut head = Coord(0, 0);
+    let (mut min, mut max) = (head.clone(), head.clone());
+    let mut motions = Vec::new();
+    for mut l in parse::lines(input) {
+        let c = l.one_of(b"RLUD")?;
+        l.literal(" ")?;
+        let column = l.column();
+        let n = l.number::<usize>()?;
+        l.end()?;
+        // keeps the coordinates of every knot from overflowing
+        let (x, y) = (*head.x(), *head.y());
+        let steps = isize::try_from(n).ok();
+        let moved = steps.and_then(|n| match c {
+            b'R' => x.checked_add(n).map(|x| Coord(x, y)),
+            b'L' => x.checked_sub(n).map(|x| Coord(x, y)),
+            b'U' => y.checked_add(n).map(|y| Coord(x, y)),
+            _ => y.checked_sub(n).map(|y| Coord(x, y)),
+        });
+        let (Some(n), Some(moved)) = (steps, moved) else {
+            return Err(l.error_at(column, "a number of steps that stays in range"));
+        };
+        head = moved;
+        min = Coord(*min.x().min(head.x()), *min.y().min(head.y()));
+        max = Coord(*max.x().max(head.x()), *max.y().max(head.y()));
+        motions.push((c, n));
+    }
+    Ok(Motions { motions, min, max })
+}
+
+/// The positions that a knot visited, in a grid of the area of the motions
+/// or in a set if the area is too large for a grid.
+enum Visited {
+    Grid {
+        seen: Vec<bool>,
+        width: usize,
+        min: Coord,
+    },
+    Set(HashSet<(isize, isize)>),
+}
+
+impl Visited {
+    fn new(motions: &Motions) -> Self {
+        let width = motions.max.x().abs_diff(*motions.min.x()).checked_add(1);
+        let height = motions.max.y().abs_diff(*motions.min.y()).checked_add(1);
+        match width.zip(height) {
+            Some((width, height)) if width.saturating_mul(height) <= MAX_GRID_AREA => {
+                Visited::Grid {
+                    seen: vec![false; width * height],
+                    width,
+                    min: motions.min.clone(),
+                }
+            }
+            _ => Visited::Set(HashSet::new()),
+        }
+    }
+
+    fn insert(&mut self, c: &Coord) {
+        match self {
+            Visited::Grid { seen, width, min } => {
+                let (x, y) = (c.x().abs_diff(*min.x()), c.y().abs_diff(*min.y()));
+                seen[y * *width + x] = true;
+            }
+            Visited::Set(seen) => {
+                seen.insert((*c.x(), *c.y()));
+            }
+        }
+    }
+
+    fn count(&self) -> usize {
+        match self {
+            Visited::Grid { seen, .. } => seen.iter().filter(|&&b| b).count(),
+            Visited::Set(seen) => seen.len(),
+        }
+    }
+}
+
+pub fn part1(motions: &Motions) -> usize {
+    let mut seen = Visited::new(motions);
     let mut head = Coord(0, 0);
     let mut tail = Coord(0, 0);
-    seen_grid[NORMALIZE_BIAS as usize][NORMALIZE_BIAS as usize] = true; // initial tail position
-    parse_input().for_each(|(c, n)| {
+    seen.insert(&tail); // initial tail position
+    motions.motions.iter().for_each(|&(c, n)| {
         // move the head
         match c {
             b'R' => *head.x_mut() += n,
@@ -81,20 +169,14 @@ pub fn part1() -> usize {
             };
 
             // if we moved the tail, update the seen grid
-            seen_grid[(tail.x() + NORMALIZE_BIAS) as usize][(tail.y() + NORMALIZE_BIAS) as usize] =
-                true;
+            seen.insert(&tail);
         }
     });
-    seen_grid
-        .iter()
-        .flat_map(|l| l.iter())
-        .filter(|&&b| b)
-        .count()
+    seen.count()
 }
 
-pub fn part2() -> usize {
-    const NORMALIZE_BIAS: isize = 112; // magic number
-    let mut seen_grid = [[false; 256]; 256]; // 64KiB "only"
+pub fn part2(motions: &Motions) -> usize {
+    let mut seen = Visited::new(motions);
     let mut knots = [
         Coord(0, 0),
         Coord(0, 0),
@@ -107,8 +189,8 @@ pub fn part2() -> usize {
         Coord(0, 0),
         Coord(0, 0),
     ];
-    seen_grid[NORMALIZE_BIAS as usize][NORMALIZE_BIAS as usize] = true; // initial tail position
-    parse_input().for_each(|(c, n)| {
+    seen.insert(&knots[9]); // initial tail position
+    motions.motions.iter().for_each(|&(c, n)| {
         for _ in 0..n {
             // For some reason, moving the head movement out of the loop
             // makes the whole thing significantly slower.
@@ -156,44 +238,156 @@ pub fn part2() -> usize {
 
                 if k == knots_count - 1 {
                     // we only care about the very tail
-                    seen_grid[(next.x() + NORMALIZE_BIAS) as usize]
-                        [(next.y() + NORMALIZE_BIAS) as usize] = true;
+                    seen.insert(next);
                 }
             }
         }
     });
-    seen_grid
-        .iter()
-        .flat_map(|l| l.iter())
-        .filter(|&&b| b)
-        .count()
+    seen.count()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Parsed<'a> = Motions;
+
+    const HAS_PARSE_STEP: bool = true;
+
+    fn parse(input: &str) -> Result<Motions, ParseError> {
+        parse_motions(input)
+    }
+
+    fn part1(motions: &Motions) -> Result<Answer, ParseError> {
+        Ok(part1(motions).into())
+    }
+
+    fn part2(motions: &Motions) -> Result<Answer, ParseError> {
+        Ok(part2(motions).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::test_input;
 
+    #[cfg(feature = "nightly")]
     extern crate test;
 
+    #[cfg(feature = "nightly")]
     use test::Bencher;
 
+    const EXAMPLE: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2
+";
+
+    const LARGER_EXAMPLE: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20
+";
+
+    #[test]
+    fn test_example() {
+        let motions = parse_motions(EXAMPLE).unwrap();
+        assert_eq!(part1(&motions), 13);
+        assert_eq!(part2(&motions), 1);
+        assert_eq!(part2(&parse_motions(LARGER_EXAMPLE).unwrap()), 36);
+    }
+
+    #[test]
+    fn test_parse_motions() {
+        let motions = parse_motions(LARGER_EXAMPLE).unwrap();
+        assert_eq!(
+            motions.motions[..5],
+            [(b'R', 5), (b'U', 8), (b'L', 8), (b'D', 3), (b'R', 17)]
+        );
+        assert_eq!((motions.min, motions.max), (Coord(-11, -5), Coord(14, 15)));
+    }
+
+    /// The grid of visited positions covers motions of any length.
+    #[test]
+    fn test_long_motions() {
+        let motions = |input| parse_motions(input).unwrap();
+        assert_eq!(part1(&motions("R 200\n")), 200);
+        assert_eq!(part2(&motions("R 200\n")), 192);
+        assert_eq!(part1(&motions("L 300\nD 300\nR 600\n")), 1198);
+        assert_eq!(part2(&motions("U 4000\n")), 3992);
+        assert!(matches!(
+            Visited::new(&motions("U 4000\n")),
+            Visited::Grid { .. }
+        ));
+    }
+
+    /// Motions that cover too large an area for a grid are tracked in a set.
+    #[test]
+    fn test_large_area() {
+        let motions = parse_motions("R 5000\nU 5000\nL 10000\n").unwrap();
+        assert!(matches!(Visited::new(&motions), Visited::Set(_)));
+        assert_eq!(part1(&motions), 19998);
+        assert_eq!(part2(&motions), 19974);
+        let far = format!("R {}\nL {}\n", isize::MAX, isize::MAX);
+        assert!(matches!(
+            Visited::new(&parse_motions(&far).unwrap()),
+            Visited::Set(_)
+        ));
+
+        let e = parse_motions(&format!("L {}\nL 2\n", isize::MAX)).unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 3, "`2`"));
+        assert_eq!(e.expected, "a number of steps that stays in range");
+        let e = parse_motions(&format!("U {}\n", usize::MAX)).unwrap_err();
+        assert_eq!((e.line, e.column), (1, 3));
+        assert_eq!(
+            parse_motions("U 99999999999999999999").unwrap_err().column,
+            3
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_motions("R 4\nX 4\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 1, "`X`"));
+        assert_eq!(e.expected, "one of `R`, `L`, `U`, `D`");
+        let e = parse_motions("R -4").unwrap_err();
+        assert_eq!((e.column, e.expected.as_str()), (3, "a number"));
+        assert_eq!(parse_motions("R 4 2").unwrap_err().column, 4);
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(), 5883);
+        let Some(input) = test_input(9) else { return };
+        assert_eq!(part1(&parse_motions(&input).unwrap()), 5883);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        b.iter(part1);
+        let Some(input) = test_input(9) else { return };
+        b.iter(|| part1(&parse_motions(&input).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(), 2367);
+        let Some(input) = test_input(9) else { return };
+        assert_eq!(part2(&parse_motions(&input).unwrap()), 2367);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        b.iter(part2);
+        let Some(input) = test_input(9) else { return };
+        b.iter(|| part2(&parse_motions(&input).unwrap()));
     }
 }
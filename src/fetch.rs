@@ -0,0 +1,479 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+
+/// The year all puzzles are from.
+pub const YEAR: u16 = 2022;
+
+/// Environment variable that overrides the default config file.
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+pub const DEFAULT_CONFIG_FILE: &str = "aoc.toml";
+/// Environment variable that overrides the session token of the config.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The time between two requests if the config doesn't set one.
+const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+/// Stores the time of the last request next to the cached inputs.
+const LAST_REQUEST_FILE: &str = ".last-request";
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (Advent of Code 2022 solutions in Rust)"
+);
+
+/// Settings of the client, e.g.
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// base_url = "http://localhost:8080"
+/// request_interval = 5.0
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The `session` cookie of a browser that is logged in.
+    pub session: Option<String>,
+    /// Where the puzzles are hosted, [`DEFAULT_BASE_URL`] if not set.
+    pub base_url: Option<String>,
+    /// The minimum number of seconds between two requests.
+    #[serde(default, deserialize_with = "request_interval")]
+    pub request_interval: Option<f64>,
+}
+
+/// Only accepts intervals that are a [`Duration`], so not negative,
+/// infinite or NaN.
+fn request_interval<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    let seconds = f64::deserialize(deserializer)?;
+    match Duration::try_from_secs_f64(seconds) {
+        Ok(_) => Ok(Some(seconds)),
+        Err(_) => Err(D::Error::custom(format!(
+            "request_interval must be a number of seconds that is 0 or more, not {seconds}"
+        ))),
+    }
+}
+
+impl Config {
+    /// Loads the config file, a missing file has no settings.
+    pub fn load(path: &Path) -> Result<Self, FetchError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content).map_err(|source| FetchError::Config {
+                path: path.to_path_buf(),
+                source,
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(FetchError::Io {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+}
+
+/// Whether [`Client::fetch`] had to download the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input file already exists and was left alone.
+    Cached,
+    /// The input was downloaded, with this many bytes.
+    Downloaded(usize),
+}
+
+/// Downloads inputs into a cache directory, which is the inputs directory.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: Option<String>,
+    limiter: RateLimiter,
+    cache_dir: PathBuf,
+}
+
+impl Client {
+    pub fn new(config: &Config, cache_dir: &Path) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+        let interval = config
+            .request_interval
+            .map_or(DEFAULT_REQUEST_INTERVAL, |s| {
+                Duration::from_secs_f64(s.max(0.0))
+            });
+        Self {
+            agent,
+            base_url: config
+                .base_url
+                .as_deref()
+                .unwrap_or(DEFAULT_BASE_URL)
+                .trim_end_matches('/')
+                .to_string(),
+            session: config.session.clone(),
+            limiter: RateLimiter {
+                interval,
+                file: cache_dir.join(LAST_REQUEST_FILE),
+            },
+            cache_dir: cache_dir.to_path_buf(),
+        }
+    }
+
+    /// The cached input of a day, `<cache dir>/day<N>.txt`.
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.cache_dir.join(format!("day{day}.txt"))
+    }
+
+    /// Downloads the input of a day unless it is cached already. An empty
+    /// file, like the placeholder of a new day, doesn't count as cached. The
+    /// session token is only needed if the input is downloaded.
+    pub fn fetch(&self, day: u8) -> Result<Fetched, FetchError> {
+        let path = self.path(day);
+        if path.metadata().is_ok_and(|m| m.len() > 0) {
+            return Ok(Fetched::Cached);
+        }
+        let input = self.get(day, &format!("/{YEAR}/day/{day}/input"))?;
+
+        // a partially written file would be taken as cached the next time
+        let io_error = |source| FetchError::Io {
+            path: path.clone(),
+            source,
+        };
+        std::fs::create_dir_all(&self.cache_dir).map_err(io_error)?;
+        let partial = path.with_extension("txt.part");
+        std::fs::write(&partial, &input).map_err(io_error)?;
+        std::fs::rename(&partial, &path).map_err(io_error)?;
+        Ok(Fetched::Downloaded(input.len()))
+    }
+
+    /// Sends an authenticated GET request, returns the body of a successful
+    /// response.
+    fn get(&self, day: u8, path: &str) -> Result<String, FetchError> {
+        let session = self.before_request()?;
+        let response = self
+            .agent
+            .get(format!("{}{path}", self.base_url))
+            .header("Cookie", format!("session={session}"))
+            .call();
+        read_body(day, response)
+    }
+
+    /// Sends an authenticated form, returns the body of a successful
+    /// response.
+    pub(crate) fn post_form(
+        &self,
+        day: u8,
+        path: &str,
+        form: &[(&str, &str)],
+    ) -> Result<String, FetchError> {
+        let session = self.before_request()?;
+        let response = self
+            .agent
+            .post(format!("{}{path}", self.base_url))
+            .header("Cookie", format!("session={session}"))
+            .send_form(form.iter().copied());
+        read_body(day, response)
+    }
+
+    /// The session token, once the rate limit allows another request.
+    fn before_request(&self) -> Result<&str, FetchError> {
+        let session = self.session.as_deref().ok_or(FetchError::MissingSession)?;
+        self.limiter.wait()?;
+        Ok(session)
+    }
+}
+
+/// The body of a response with status 200, any other status is an error.
+fn read_body(
+    day: u8,
+    response: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+) -> Result<String, FetchError> {
+    let mut response = response.map_err(|source| FetchError::Request { day, source })?;
+    let status = response.status().as_u16();
+    let body = response
+        .body_mut()
+        .read_to_string()
+        .map_err(|source| FetchError::Request { day, source })?;
+    if status != 200 {
+        let message = body.lines().next().unwrap_or_default().trim().to_string();
+        return Err(FetchError::Http {
+            day,
+            status,
+            message,
+        });
+    }
+    Ok(body)
+}
+
+/// Keeps at least `interval` between two requests, also across runs of the
+/// binary by storing the time of the last request in `file`.
+struct RateLimiter {
+    interval: Duration,
+    file: PathBuf,
+}
+
+impl RateLimiter {
+    fn wait(&self) -> Result<(), FetchError> {
+        let last = std::fs::read_to_string(&self.file)
+            .ok()
+            .and_then(|nanos| nanos.trim().parse().ok())
+            .map(|nanos| UNIX_EPOCH + Duration::from_nanos(nanos));
+        if let Some(last) = last {
+            // a last request in the future means the clock was changed
+            let elapsed = SystemTime::now()
+                .duration_since(last)
+                .unwrap_or(Duration::ZERO);
+            std::thread::sleep(self.interval.saturating_sub(elapsed));
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        self.file
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&self.file, now.to_string()))
+            .map_err(|source| FetchError::Io {
+                path: self.file.clone(),
+                source,
+            })
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    Config {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    MissingSession,
+    Request {
+        day: u8,
+        source: ureq::Error,
+    },
+    /// The server answered with another status than 200.
+    Http {
+        day: u8,
+        status: u16,
+        /// The first line of the body.
+        message: String,
+    },
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Config { path, source } => {
+                write!(f, "invalid config file {}: {source}", path.display())
+            }
+            FetchError::Io { path, source } => {
+                write!(f, "cannot access {}: {source}", path.display())
+            }
+            FetchError::MissingSession => write!(
+                f,
+                "no session token, set `session` in the config file or {SESSION_ENV}"
+            ),
+            FetchError::Request { day, source } => write!(f, "day {day}: request failed: {source}"),
+            FetchError::Http {
+                day,
+                status: 404,
+                message,
+            } => write!(f, "day {day}: the puzzle is not available (404): {message}"),
+            FetchError::Http {
+                day,
+                status: status @ (400 | 500),
+                message,
+            } => write!(
+                f,
+                "day {day}: the server answered {status}, is the session token still valid? {message}"
+            ),
+            FetchError::Http {
+                day,
+                status,
+                message,
+            } => write!(f, "day {day}: the server answered {status}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FetchError::Config { source, .. } => Some(source),
+            FetchError::Io { source, .. } => Some(source),
+            FetchError::Request { source, .. } => Some(source),
+            FetchError::MissingSession | FetchError::Http { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Receiver};
+    use std::time::Instant;
+
+    use super::*;
+
+    /// A stub server that answers one request per response, in order, and
+    /// sends the head and body of every request it received.
+    pub(crate) fn serve(responses: Vec<(u16, &'static str)>) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    request += &line;
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut content = vec![0; content_length];
+                std::io::Read::read_exact(&mut reader, &mut content).unwrap();
+                request += &String::from_utf8(content).unwrap();
+                tx.send(request).unwrap();
+                write!(
+                    &stream,
+                    "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        (base_url, rx)
+    }
+
+    /// A new empty directory that is unique to the test.
+    pub(crate) fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn config(base_url: String) -> Config {
+        Config {
+            session: Some("abc".to_string()),
+            base_url: Some(base_url + "/"),
+            request_interval: Some(0.0),
+        }
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (base_url, requests) = serve(vec![(200, "1\n2\n"), (200, "3\n")]);
+        let dir = temp_dir("fetch");
+        let client = Client::new(&config(base_url), &dir);
+
+        assert_eq!(client.fetch(3).unwrap(), Fetched::Downloaded(4));
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.starts_with("get /2022/day/3/input http/1.1\r\n"));
+        assert!(request.contains("\r\ncookie: session=abc\r\n"));
+        assert!(request.contains("\r\nuser-agent: aoc/"));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("day3.txt")).unwrap(),
+            "1\n2\n"
+        );
+
+        // the stub only answers twice
+        assert_eq!(client.fetch(3).unwrap(), Fetched::Cached);
+
+        // the placeholder of a new day is replaced
+        std::fs::write(dir.join("day4.txt"), "").unwrap();
+        assert_eq!(client.fetch(4).unwrap(), Fetched::Downloaded(2));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("day4.txt")).unwrap(),
+            "3\n"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let (base_url, _requests) = serve(vec![(404, "Not Found\nmore"), (500, "Internal")]);
+        let dir = temp_dir("fetch-errors");
+        let client = Client::new(&config(base_url.clone()), &dir);
+        let e = client.fetch(26).unwrap_err();
+        assert!(matches!(e, FetchError::Http { status: 404, .. }));
+        assert_eq!(
+            e.to_string(),
+            "day 26: the puzzle is not available (404): Not Found"
+        );
+        let e = client.fetch(1).unwrap_err();
+        assert!(e.to_string().contains("is the session token still valid?"));
+        assert!(!dir.join("day1.txt").exists());
+
+        let config = Config {
+            session: None,
+            ..config(base_url)
+        };
+        let e = Client::new(&config, &dir).fetch(1).unwrap_err();
+        assert!(matches!(e, FetchError::MissingSession));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let dir = temp_dir("rate-limit");
+        let limiter = RateLimiter {
+            interval: Duration::from_millis(100),
+            file: dir.join(LAST_REQUEST_FILE),
+        };
+        let start = Instant::now();
+        limiter.wait().unwrap();
+        assert!(start.elapsed() < Duration::from_millis(100));
+        limiter.wait().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_config() {
+        let config: Config = toml::from_str("session = \"abc\"\nrequest_interval = 1.5\n").unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert_eq!(config.base_url, None);
+        assert!(toml::from_str::<Config>("sesion = \"abc\"\n").is_err());
+        assert_eq!(toml::from_str::<Config>("").unwrap(), Config::default());
+        for interval in ["inf", "nan", "-1.0", "1e300"] {
+            let e =
+                toml::from_str::<Config>(&format!("request_interval = {interval}\n")).unwrap_err();
+            assert!(
+                e.to_string()
+                    .contains("request_interval must be a number of seconds"),
+                "{e}"
+            );
+        }
+
+        let dir = temp_dir("config");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("aoc.toml");
+        std::fs::write(&path, "request_interval = inf\n").unwrap();
+        let e = Config::load(&path).unwrap_err();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(e, FetchError::Config { .. }));
+        assert_eq!(
+            Config::load(Path::new("does/not/exist.toml")).unwrap(),
+            Config::default()
+        );
+    }
+}
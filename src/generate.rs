@@ -0,0 +1,831 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter, Write};
+
+use itertools::Itertools;
+use rand::rngs::Xoshiro256PlusPlus;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{RngExt, SeedableRng};
+
+/// The random number generator of all generators, its sequence only
+/// depends on the seed.
+pub type Random = Xoshiro256PlusPlus;
+
+/// Creates random inputs of a day that its solution can solve.
+pub struct Generator {
+    pub day: u8,
+    /// The size of a real input.
+    pub size: usize,
+    /// What the size counts, e.g. `elves`.
+    pub unit: &'static str,
+    generate: fn(&mut Random, usize) -> String,
+}
+
+impl Generator {
+    /// The input for a seed, the same seed and size always result in the
+    /// same input. Sizes the day cannot handle are clamped to the nearest
+    /// one it can.
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        (self.generate)(&mut Random::seed_from_u64(seed), size)
+    }
+}
+
+/// The generators of all solved days, ordered by day.
+static GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        size: 250,
+        unit: "elves, at least 3",
+        generate: calories,
+    },
+    Generator {
+        day: 2,
+        size: 2500,
+        unit: "rounds",
+        generate: strategy_guide,
+    },
+    Generator {
+        day: 3,
+        size: 100,
+        unit: "groups of three rucksacks",
+        generate: rucksacks,
+    },
+    Generator {
+        day: 4,
+        size: 1000,
+        unit: "pairs of elves",
+        generate: assignments,
+    },
+    Generator {
+        day: 5,
+        size: 500,
+        unit: "moves",
+        generate: stacks,
+    },
+    Generator {
+        day: 6,
+        size: 4096,
+        unit: "letters, at least 14",
+        generate: datastream,
+    },
+    Generator {
+        day: 7,
+        size: 180,
+        unit: "directories",
+        generate: terminal,
+    },
+    Generator {
+        day: 8,
+        size: 99,
+        unit: "rows and columns",
+        generate: trees,
+    },
+    Generator {
+        day: 9,
+        size: 2000,
+        unit: "motions",
+        generate: motions,
+    },
+    Generator {
+        day: 10,
+        size: 240,
+        unit: "cycles",
+        generate: program,
+    },
+    Generator {
+        day: 11,
+        size: 8,
+        unit: "monkeys, 2 to 9",
+        generate: monkeys,
+    },
+    Generator {
+        day: 12,
+        size: 41,
+        unit: "rows",
+        generate: heightmap,
+    },
+    Generator {
+        day: 13,
+        size: 150,
+        unit: "pairs of packets",
+        generate: packets,
+    },
+    Generator {
+        day: 14,
+        size: 150,
+        unit: "rock paths",
+        generate: rocks,
+    },
+];
+
+pub fn generators() -> &'static [Generator] {
+    GENERATORS
+}
+
+pub fn get(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+/// Day 1: groups of calories, every elf carries at least one item.
+fn calories(rng: &mut Random, elves: usize) -> String {
+    (0..elves.max(3))
+        .map(|_| {
+            let items = rng.random_range(1..=15);
+            (0..items)
+                .map(|_| format!("{}\n", rng.random_range(1000..=65000)))
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+/// Day 2: the shape of the opponent and `X`, `Y` or `Z`.
+fn strategy_guide(rng: &mut Random, rounds: usize) -> String {
+    (0..rounds.max(1))
+        .map(|_| {
+            let left = *b"ABC".choose(rng).unwrap() as char;
+            let right = *b"XYZ".choose(rng).unwrap() as char;
+            format!("{left} {right}\n")
+        })
+        .collect()
+}
+
+/// Day 3: the compartments of every rucksack share exactly one item, and
+/// the rucksacks of a group exactly one badge.
+fn rucksacks(rng: &mut Random, groups: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..groups.max(1) {
+        let mut items = (b'a'..=b'z').chain(b'A'..=b'Z').collect::<Vec<_>>();
+        items.shuffle(rng);
+        let badge = items.pop().unwrap();
+        // every other item only goes into one rucksack of the group
+        for owned in items.chunks(items.len() / 3) {
+            let (shared, owned) = if rng.random_bool(0.2) {
+                (badge, owned)
+            } else {
+                (owned[0], &owned[1..])
+            };
+            let (left_only, right_only) = owned.split_at(owned.len() / 2);
+            let badge_left = rng.random_bool(0.5);
+            let len = rng.random_range(4..=16);
+            let mut compartment = |only: &[u8], has_badge: bool| {
+                let mut compartment = vec![shared];
+                if has_badge && shared != badge {
+                    compartment.push(badge);
+                }
+                while compartment.len() < len {
+                    compartment.push(*only.choose(rng).unwrap());
+                }
+                compartment.shuffle(rng);
+                compartment
+            };
+            let left = compartment(left_only, badge_left);
+            let right = compartment(right_only, !badge_left);
+            input.extend(left.iter().chain(&right).map(|&b| b as char));
+            input.push('\n');
+        }
+    }
+    input
+}
+
+/// Day 4: two ranges of sections from 1 to 99 per line.
+fn assignments(rng: &mut Random, pairs: usize) -> String {
+    let range = |rng: &mut Random| {
+        let start = rng.random_range(1..=99);
+        (start, rng.random_range(start..=99))
+    };
+    (0..pairs.max(1))
+        .map(|_| {
+            let (left, right) = (range(rng), range(rng));
+            format!("{}-{},{}-{}\n", left.0, left.1, right.0, right.1)
+        })
+        .collect()
+}
+
+/// Day 5: nine stacks and moves that always leave a crate on a stack, so
+/// there is a crate on top of every stack in the end.
+fn stacks(rng: &mut Random, moves: usize) -> String {
+    let mut stacks = (0..9)
+        .map(|_| {
+            let height = rng.random_range(2..=8);
+            (0..height)
+                .map(|_| rng.random_range(b'A'..=b'Z'))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut input = String::new();
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    for level in (0..height).rev() {
+        let line = stacks.iter().map(|stack| match stack.get(level) {
+            Some(&c) => format!("[{}]", c as char),
+            None => "   ".to_string(),
+        });
+        writeln!(input, "{}", line.format(" ")).unwrap();
+    }
+    let numbers = (1..=stacks.len()).map(|n| format!(" {n} "));
+    writeln!(input, "{}\n", numbers.format(" ")).unwrap();
+
+    for _ in 0..moves.max(1) {
+        let from = loop {
+            let from = rng.random_range(0..stacks.len());
+            if stacks[from].len() > 1 {
+                break from;
+            }
+        };
+        let to = (from + rng.random_range(1..stacks.len())) % stacks.len();
+        let height = stacks[from].len();
+        let count = rng.random_range(1..height);
+        let crates = stacks[from].split_off(height - count);
+        stacks[to].extend(crates);
+        writeln!(input, "move {count} from {} to {}", from + 1, to + 1).unwrap();
+    }
+    input
+}
+
+/// Day 6: a stream that starts with only three different letters, so the
+/// markers come later, and has a marker of 14 different letters.
+fn datastream(rng: &mut Random, len: usize) -> String {
+    let len = len.max(14);
+    let mut letters = (b'a'..=b'z').collect::<Vec<_>>();
+    letters.shuffle(rng);
+    let prefix = rng.random_range(0..=len / 2);
+    let mut stream = (0..len)
+        .map(|i| {
+            let letters = if i < prefix { &letters[..3] } else { &letters };
+            *letters.choose(rng).unwrap()
+        })
+        .collect::<Vec<_>>();
+    if !stream.windows(14).any(|window| window.iter().all_unique()) {
+        let marker = &mut stream[len - 14..];
+        marker.copy_from_slice(&letters[..14]);
+        marker.shuffle(rng);
+    }
+    String::from_utf8(stream).unwrap() + "\n"
+}
+
+/// A directory of day 7.
+#[derive(Default)]
+struct Directory {
+    name: String,
+    directories: Vec<usize>,
+    files: Vec<(usize, String)>,
+}
+
+/// Day 7: a tree of directories that is listed once, depth first. Most
+/// disks are at least 40,000,000 full, so part 2 has to free up space.
+fn terminal(rng: &mut Random, directories: usize) -> String {
+    let mut tree = vec![Directory {
+        name: "/".to_string(),
+        ..Directory::default()
+    }];
+    // only files have extensions
+    let names = |rng: &mut Random, taken: &mut Vec<String>, file: bool| loop {
+        let len = rng.random_range(1..=8);
+        let mut name = (0..len)
+            .map(|_| rng.random_range(b'a'..=b'z') as char)
+            .collect::<String>();
+        if file && rng.random_bool(0.5) {
+            name += [".txt", ".dat", ".log", ".lst"].choose(rng).unwrap();
+        }
+        if !taken.contains(&name) {
+            taken.push(name.clone());
+            break name;
+        }
+    };
+    let mut taken = vec![Vec::new()];
+    for i in 1..directories.max(1) {
+        let parent = rng.random_range(0..i);
+        let name = names(rng, &mut taken[parent], false);
+        tree.push(Directory {
+            name,
+            ..Directory::default()
+        });
+        tree[parent].directories.push(i);
+        taken.push(Vec::new());
+    }
+    let mut total = 0;
+    for (dir, taken) in tree.iter_mut().zip(&mut taken) {
+        for _ in 0..rng.random_range(0..=4) {
+            let size = rng.random_range(1000..=300_000);
+            dir.files.push((size, names(rng, taken, true)));
+            total += size;
+        }
+    }
+    if total <= 40_000_000 && rng.random_bool(0.9) {
+        let size = 40_000_000 - total + rng.random_range(1..=10_000_000);
+        tree[0].files.push((size, names(rng, &mut taken[0], true)));
+    }
+
+    let mut input = String::new();
+    list(rng, &tree, 0, &mut input);
+    // the transcript ends in the last directory
+    while let Some(rest) = input.strip_suffix("$ cd ..\n") {
+        input.truncate(rest.len());
+    }
+    input
+}
+
+/// Lists a directory, enters its subdirectories and leaves it again.
+fn list(rng: &mut Random, tree: &[Directory], dir: usize, input: &mut String) {
+    let directory = &tree[dir];
+    writeln!(input, "$ cd {}\n$ ls", directory.name).unwrap();
+    let mut entries = directory
+        .directories
+        .iter()
+        .map(|&d| format!("dir {}", tree[d].name))
+        .chain(
+            directory
+                .files
+                .iter()
+                .map(|(size, name)| format!("{size} {name}")),
+        )
+        .collect::<Vec<_>>();
+    entries.shuffle(rng);
+    for entry in entries {
+        writeln!(input, "{entry}").unwrap();
+    }
+    for &d in &directory.directories {
+        list(rng, tree, d, input);
+        input.push_str("$ cd ..\n");
+    }
+}
+
+/// Day 8: a square grid of tree heights, the trees in the middle tend to
+/// be higher.
+fn trees(rng: &mut Random, size: usize) -> String {
+    let size = size.max(1);
+    let center = (size - 1) as f64 / 2.0;
+    let mut input = String::with_capacity(size * (size + 1));
+    for row in 0..size {
+        for col in 0..size {
+            let distance = (row as f64 - center).abs().max((col as f64 - center).abs());
+            let bias = 4.0 * (1.0 - distance / center.max(1.0));
+            let height = (rng.random_range(0.0..6.0) + bias) as u8;
+            input.push((b'0' + height.min(9)) as char);
+        }
+        input.push('\n');
+    }
+    input
+}
+
+/// Day 9: motions of up to 19 steps like in real inputs, and now and then
+/// a much longer one. They keep the rope within 1500 steps of the start.
+fn motions(rng: &mut Random, motions: usize) -> String {
+    const BOUNDS: std::ops::RangeInclusive<i64> = -1500..=1500;
+    let (mut x, mut y) = (0_i64, 0_i64);
+    let mut input = String::new();
+    for _ in 0..motions.max(1) {
+        loop {
+            let direction = *b"RLUD".choose(rng).unwrap();
+            let steps = match rng.random_bool(0.02) {
+                true => rng.random_range(20..=400),
+                false => rng.random_range(1..=19),
+            };
+            let (dx, dy) = match direction {
+                b'R' => (steps, 0),
+                b'L' => (-steps, 0),
+                b'U' => (0, steps),
+                _ => (0, -steps),
+            };
+            if BOUNDS.contains(&(x + dx)) && BOUNDS.contains(&(y + dy)) {
+                (x, y) = (x + dx, y + dy);
+                writeln!(input, "{} {steps}", direction as char).unwrap();
+                break;
+            }
+        }
+    }
+    input
+}
+
+/// Day 10: a program that takes exactly the given number of cycles, the
+/// screen shows the first 240 of them. The sprite moves a bit beyond the
+/// screen at times.
+fn program(rng: &mut Random, cycles: usize) -> String {
+    let cycles = cycles.max(1);
+    let mut x = 1_i64;
+    let mut cycle = 0;
+    let mut input = String::new();
+    while cycle < cycles {
+        if cycles - cycle >= 2 && rng.random_bool(0.6) {
+            let target = (x + rng.random_range(-10..=10)).clamp(-10, 50);
+            writeln!(input, "addx {}", target - x).unwrap();
+            x = target;
+            cycle += 2;
+        } else {
+            input.push_str("noop\n");
+            cycle += 1;
+        }
+    }
+    input
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    Add(usize),
+    Mul(usize),
+    Square,
+}
+
+struct Monkey {
+    items: Vec<usize>,
+    operation: Operation,
+    divisor: usize,
+    targets: [usize; 2],
+}
+
+/// Day 11: monkeys with different prime divisors, so their product stays
+/// small enough for squaring a worry level. The worry levels of part 1
+/// are checked to fit into a `usize`.
+fn monkeys(rng: &mut Random, monkeys: usize) -> String {
+    const PRIMES: [usize; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    let count = monkeys.clamp(2, PRIMES.len());
+    let monkeys = (0..)
+        .map(|attempt| {
+            let mut divisors = PRIMES;
+            divisors.shuffle(rng);
+            // without squaring the worry levels go down in part 1
+            let square = (attempt < 100).then(|| rng.random_range(0..count));
+            (0..count)
+                .map(|i| {
+                    let operation = if square == Some(i) {
+                        Operation::Square
+                    } else if rng.random_bool(0.5) {
+                        Operation::Mul(rng.random_range(2..=19))
+                    } else {
+                        Operation::Add(rng.random_range(1..=8))
+                    };
+                    let items = rng.random_range(1..=8);
+                    Monkey {
+                        items: (0..items).map(|_| rng.random_range(50..=99)).collect(),
+                        operation,
+                        divisor: divisors[i],
+                        targets: [0; 2].map(|_| (i + rng.random_range(1..count)) % count),
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .find(|monkeys| part1_fits(monkeys))
+        .unwrap();
+
+    monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, monkey)| {
+            let operation = match monkey.operation {
+                Operation::Add(n) => format!("+ {n}"),
+                Operation::Mul(n) => format!("* {n}"),
+                Operation::Square => "* old".to_string(),
+            };
+            format!(
+                "Monkey {i}:
+  Starting items: {}
+  Operation: new = old {operation}
+  Test: divisible by {}
+    If true: throw to monkey {}
+    If false: throw to monkey {}
+",
+                monkey.items.iter().format(", "),
+                monkey.divisor,
+                monkey.targets[0],
+                monkey.targets[1]
+            )
+        })
+        .join("\n")
+}
+
+/// Whether the 20 rounds of part 1 never overflow.
+fn part1_fits(monkeys: &[Monkey]) -> bool {
+    let mut items = monkeys
+        .iter()
+        .map(|m| m.items.iter().copied().collect::<VecDeque<_>>())
+        .collect::<Vec<_>>();
+    for _ in 0..20 {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            while let Some(item) = items[i].pop_front() {
+                let item = match monkey.operation {
+                    Operation::Add(n) => item.checked_add(n),
+                    Operation::Mul(n) => item.checked_mul(n),
+                    Operation::Square => item.checked_mul(item),
+                };
+                let Some(item) = item.map(|item| item / 3) else {
+                    return false;
+                };
+                let target = monkey.targets[usize::from(!item.is_multiple_of(monkey.divisor))];
+                items[target].push_back(item);
+            }
+        }
+    }
+    true
+}
+
+/// Day 12: the elevation rises towards the end by at most one per step,
+/// with some random dips that are checked to leave a path from the start.
+fn heightmap(rng: &mut Random, rows: usize) -> String {
+    // the start and the end are at least 25 steps apart
+    let rows = rows.max(1);
+    let cols = (4 * rows).max(26);
+    let (end_row, end_col) = loop {
+        let end = (rng.random_range(0..rows), rng.random_range(0..cols));
+        if farthest(end, rows, cols) >= 25 {
+            break end;
+        }
+    };
+    let scale = farthest((end_row, end_col), rows, cols) / 25;
+    let rise = |row: usize, col: usize| {
+        let distance = row.abs_diff(end_row) + col.abs_diff(end_col);
+        25 - (distance / scale).min(25) as u8
+    };
+    let lowest = (0..rows)
+        .cartesian_product(0..cols)
+        .filter(|&(row, col)| rise(row, col) == 0)
+        .collect::<Vec<_>>();
+    let start = *lowest.choose(rng).unwrap();
+
+    for attempt in 0.. {
+        let dips = if attempt < 10 { 0.1 } else { 0.0 };
+        let mut map = (0..rows)
+            .map(|row| {
+                (0..cols)
+                    .map(|col| {
+                        let elevation = rise(row, col);
+                        match rng.random_bool(dips) {
+                            true => elevation.saturating_sub(rng.random_range(1..=3)),
+                            false => elevation,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        map[start.0][start.1] = 0;
+        map[end_row][end_col] = 25;
+        if !climbable(&map, start, (end_row, end_col)) {
+            continue;
+        }
+        let mut input = String::with_capacity(rows * (cols + 1));
+        for (row, line) in map.iter().enumerate() {
+            for (col, &elevation) in line.iter().enumerate() {
+                input.push(match (row, col) {
+                    p if p == start => 'S',
+                    p if p == (end_row, end_col) => 'E',
+                    _ => (b'a' + elevation) as char,
+                });
+            }
+            input.push('\n');
+        }
+        return input;
+    }
+    unreachable!("the map without dips is always climbable")
+}
+
+/// The distance to the farthest corner.
+fn farthest((row, col): (usize, usize), rows: usize, cols: usize) -> usize {
+    row.max(rows - 1 - row) + col.max(cols - 1 - col)
+}
+
+/// Whether the end can be reached from the start by climbing at most one
+/// elevation per step.
+fn climbable(map: &[Vec<u8>], start: (usize, usize), end: (usize, usize)) -> bool {
+    let mut seen = vec![vec![false; map[0].len()]; map.len()];
+    let mut queue = VecDeque::from([start]);
+    seen[start.0][start.1] = true;
+    while let Some((row, col)) = queue.pop_front() {
+        if (row, col) == end {
+            return true;
+        }
+        let neighbours = [
+            (row.wrapping_sub(1), col),
+            (row + 1, col),
+            (row, col.wrapping_sub(1)),
+            (row, col + 1),
+        ];
+        for (r, c) in neighbours {
+            let Some(&elevation) = map.get(r).and_then(|line| line.get(c)) else {
+                continue;
+            };
+            if !seen[r][c] && elevation <= map[row][col] + 1 {
+                seen[r][c] = true;
+                queue.push_back((r, c));
+            }
+        }
+    }
+    false
+}
+
+/// A packet of day 13.
+#[derive(Debug, Clone)]
+enum Packet {
+    List(Vec<Packet>),
+    Number(u8),
+}
+
+impl Packet {
+    fn random(rng: &mut Random, depth: usize) -> Self {
+        let len = rng.random_range(0..=5);
+        Packet::List(
+            (0..len)
+                .map(|_| match depth < 4 && rng.random_bool(0.3) {
+                    true => Packet::random(rng, depth + 1),
+                    false => Packet::Number(rng.random_range(0..=10)),
+                })
+                .collect(),
+        )
+    }
+
+    /// Changes a number, wraps it into a list that starts with it, possibly
+    /// nested, or changes the length of a list, so that the other packet of
+    /// a pair is similar.
+    fn mutate(&mut self, rng: &mut Random) {
+        match self {
+            Packet::Number(n) if rng.random_bool(0.5) => *n = rng.random_range(0..=10),
+            Packet::Number(n) => {
+                let mut list = vec![Packet::Number(*n)];
+                if rng.random_bool(0.5) {
+                    list.push(Packet::Number(rng.random_range(0..=10)));
+                }
+                *self = match rng.random_bool(0.5) {
+                    true => Packet::List(vec![Packet::List(list)]),
+                    false => Packet::List(list),
+                };
+            }
+            Packet::List(list) if list.is_empty() || rng.random_bool(0.3) => match list.pop() {
+                Some(_) if rng.random_bool(0.5) => {}
+                _ => list.push(Packet::Number(rng.random_range(0..=10))),
+            },
+            Packet::List(list) => {
+                let i = rng.random_range(0..list.len());
+                list[i].mutate(rng);
+            }
+        }
+    }
+}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Packet::List(list) => write!(f, "[{}]", list.iter().format(",")),
+            Packet::Number(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+/// Day 13: pairs of packets, half of them differ only slightly.
+fn packets(rng: &mut Random, pairs: usize) -> String {
+    (0..pairs.max(1))
+        .map(|_| {
+            let left = Packet::random(rng, 0);
+            let right = if rng.random_bool(0.5) {
+                let mut right = left.clone();
+                for _ in 0..rng.random_range(1..=2) {
+                    right.mutate(rng);
+                }
+                right
+            } else {
+                Packet::random(rng, 0)
+            };
+            format!("{left}\n{right}\n")
+        })
+        .join("\n")
+}
+
+/// Day 14: paths of horizontal and vertical lines. Like in real inputs,
+/// every rock structure is described by about five paths, and half of them
+/// are cups that fill up with sand. Some structures are lower or farther
+/// to the side than in real inputs, out of reach of the sand.
+fn rocks(rng: &mut Random, paths: usize) -> String {
+    const X: std::ops::RangeInclusive<i64> = 0..=1000;
+    const Y: std::ops::RangeInclusive<i64> = 5..=200;
+    let paths = paths.max(1);
+    let structures = (0..paths.div_ceil(5))
+        .map(|_| {
+            let x = match rng.random_bool(0.1) {
+                true => rng.random_range(250..=750),
+                false => rng.random_range(470..=530),
+            };
+            let y = rng.random_range(13..=195);
+            let points = if rng.random_bool(0.5) {
+                let (width, depth) = (rng.random_range(3..=12), rng.random_range(2..=7));
+                vec![
+                    (x, y),
+                    (x, y + depth),
+                    (x + width, y + depth),
+                    (x + width, y),
+                ]
+            } else {
+                let mut point = (x, y);
+                let mut points = vec![point];
+                let mut horizontal = rng.random_bool(0.5);
+                for _ in 0..rng.random_range(1..=6) {
+                    let step = rng.random_range(1..=8) * if rng.random_bool(0.5) { 1 } else { -1 };
+                    point = match horizontal {
+                        true => (point.0 + step, point.1),
+                        false => (point.0, point.1 + step),
+                    };
+                    points.push(point);
+                    horizontal = !horizontal;
+                }
+                points
+            };
+            points
+                .iter()
+                .map(|&(x, y)| (x.clamp(*X.start(), *X.end()), y.clamp(*Y.start(), *Y.end())))
+                .map(|(x, y)| format!("{x},{y}"))
+                .join(" -> ")
+                + "\n"
+        })
+        .collect::<Vec<_>>();
+    let mut lines = structures.iter().cycle().take(paths).collect::<Vec<_>>();
+    lines.shuffle(rng);
+    lines.into_iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    /// A new day doesn't need a generator right away, but every generator
+    /// belongs to a solved day.
+    #[test]
+    fn test_generators() {
+        assert!(GENERATORS.windows(2).all(|w| w[0].day < w[1].day));
+        for generator in generators() {
+            assert!(
+                registry::get(generator.day).is_some(),
+                "day {} is not solved",
+                generator.day
+            );
+        }
+        assert!(get(1).is_some());
+        assert!(get(25).is_none());
+    }
+
+    #[test]
+    fn test_deterministic() {
+        for generator in generators() {
+            let input = generator.generate(7, 10);
+            assert_eq!(input, generator.generate(7, 10), "day {}", generator.day);
+            assert_ne!(input, generator.generate(8, 10), "day {}", generator.day);
+        }
+    }
+
+    /// Every generated input can be solved, from the smallest size up to
+    /// the size of a real input.
+    #[test]
+    fn test_solvable() {
+        for generator in generators() {
+            let day = registry::get(generator.day).unwrap();
+            for (seed, size) in (0..4).cartesian_product([0, 1, 2, 5, generator.size]) {
+                let input = generator.generate(seed, size);
+                let parsed = day
+                    .parse(&input)
+                    .unwrap_or_else(|e| panic!("day {}, seed {seed}, size {size}: {e}", day.day()));
+                for part in day.parts() {
+                    if let Err(e) = parsed.solve(part) {
+                        panic!("day {}, seed {seed}, size {size}: {e}", day.day());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sizes() {
+        let lines = |day, size| get(day).unwrap().generate(0, size).lines().count();
+        assert_eq!(lines(2, 30), 30);
+        assert_eq!(lines(3, 30), 90);
+        assert_eq!(lines(4, 30), 30);
+        assert_eq!(lines(8, 30), 30);
+        assert_eq!(lines(8, 300), 300);
+        assert_eq!(lines(11, 3), 3 * 7 - 1);
+        assert_eq!(lines(11, 30), 9 * 7 - 1);
+        assert_eq!(lines(12, 10), 10);
+        assert_eq!(lines(13, 30), 3 * 30 - 1);
+        assert_eq!(lines(14, 30), 30);
+
+        let input = get(1).unwrap().generate(0, 30);
+        assert_eq!(input.split("\n\n").count(), 30);
+        let input = get(6).unwrap().generate(0, 30);
+        assert_eq!(input.trim_end().len(), 30);
+        let input = get(12).unwrap().generate(0, 10);
+        assert!(input.lines().all(|l| l.len() == 40));
+
+        // the program of day 10 takes exactly the given number of cycles
+        let cycles = |input: &str| {
+            input
+                .lines()
+                .map(|l| if l == "noop" { 1 } else { 2 })
+                .sum::<usize>()
+        };
+        assert_eq!(cycles(&get(10).unwrap().generate(0, 100)), 100);
+        assert_eq!(cycles(&get(10).unwrap().generate(0, 1000)), 1000);
+    }
+
+    #[test]
+    fn test_packet() {
+        let packet = Packet::List(vec![
+            Packet::Number(1),
+            Packet::List(vec![]),
+            Packet::List(vec![Packet::Number(10)]),
+        ]);
+        assert_eq!(packet.to_string(), "[1,[],[10]]");
+    }
+}
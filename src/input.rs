@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that overrides the default inputs directory.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+const DEFAULT_INPUT_DIR: &str = "inputs";
+
+/// Where the input for a single day comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `<dir>/day<N>.txt`
+    Dir(PathBuf),
+    /// An explicit file.
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Parses a command line value, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            Self::Stdin
+        } else {
+            Self::File(PathBuf::from(arg))
+        }
+    }
+
+    /// The name that answers for this input are stored under.
+    pub fn name(&self) -> String {
+        match self {
+            InputSource::Dir(_) => "default".to_string(),
+            InputSource::File(path) => path
+                .file_stem()
+                .map_or_else(|| "file".to_string(), |s| s.to_string_lossy().into_owned()),
+            InputSource::Stdin => "stdin".to_string(),
+        }
+    }
+
+    /// The file the input of `day` is read from, `None` for stdin.
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::Dir(dir) => Some(dir.join(format!("day{day}.txt"))),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    pub fn read(&self, day: u8) -> Result<String, InputError> {
+        match self {
+            InputSource::Dir(dir) => read_file(day, &dir.join(format!("day{day}.txt"))),
+            InputSource::File(path) => read_file(day, path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|source| InputError::Stdin { day, source })?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+fn read_file(day: u8, path: &Path) -> Result<String, InputError> {
+    std::fs::read_to_string(path).map_err(|source| {
+        if source.kind() == std::io::ErrorKind::NotFound {
+            InputError::Missing {
+                day,
+                path: path.to_path_buf(),
+            }
+        } else {
+            InputError::Io {
+                day,
+                path: path.to_path_buf(),
+                source,
+            }
+        }
+    })
+}
+
+/// Resolves the input of every day, either from the inputs directory or
+/// from a per-day override.
+#[derive(Debug, Clone)]
+pub struct Inputs {
+    dir: PathBuf,
+    overrides: HashMap<u8, InputSource>,
+}
+
+impl Default for Inputs {
+    fn default() -> Self {
+        Self::new(default_dir())
+    }
+}
+
+impl Inputs {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            overrides: HashMap::new(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn set_override(&mut self, day: u8, source: InputSource) {
+        self.overrides.insert(day, source);
+    }
+
+    pub fn source(&self, day: u8) -> InputSource {
+        self.overrides
+            .get(&day)
+            .cloned()
+            .unwrap_or_else(|| InputSource::Dir(self.dir.clone()))
+    }
+
+    pub fn load(&self, day: u8) -> Result<String, InputError> {
+        self.source(day).read(day)
+    }
+
+    /// The directory with the corpus of a day, `<dir>/day<N>`.
+    pub fn corpus_dir(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{day}"))
+    }
+
+    /// Every `.txt` file in the corpus of a day, ordered by name. A day
+    /// without a corpus directory has an empty corpus.
+    pub fn corpus(&self, day: u8) -> Result<Vec<InputSource>, InputError> {
+        let dir = self.corpus_dir(day);
+        let io_error = |source| InputError::Io {
+            day,
+            path: dir.clone(),
+            source,
+        };
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(source) => return Err(io_error(source)),
+        };
+        let mut files = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(io_error)?.path();
+            if path.extension().is_some_and(|e| e == "txt") {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files.into_iter().map(InputSource::File).collect())
+    }
+}
+
+/// The inputs directory, taken from `AOC_INPUT_DIR` if set.
+pub fn default_dir() -> PathBuf {
+    std::env::var_os(INPUT_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing {
+        day: u8,
+        path: PathBuf,
+    },
+    Io {
+        day: u8,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Stdin {
+        day: u8,
+        source: std::io::Error,
+    },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing { day, path } => write!(
+                f,
+                "day {day}: input file {} not found (use --input-dir or {INPUT_DIR_ENV} to point to your inputs)",
+                path.display()
+            ),
+            InputError::Io { day, path, source } => {
+                write!(f, "day {day}: cannot read {}: {source}", path.display())
+            }
+            InputError::Stdin { day, source } => {
+                write!(f, "day {day}: cannot read input from stdin: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Missing { .. } => None,
+            InputError::Io { source, .. } | InputError::Stdin { source, .. } => Some(source),
+        }
+    }
+}
+
+/// The inputs directory of tests and benches.
+#[cfg(test)]
+pub fn test_dir() -> PathBuf {
+    std::env::var_os(INPUT_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_INPUT_DIR))
+}
+
+/// Loads the private input of a day for tests and benches.
+///
+/// Returns `None` if the input is not available or is still the empty
+/// placeholder of a new day, in which case the test is skipped.
+#[cfg(test)]
+pub fn test_input(day: u8) -> Option<String> {
+    match InputSource::Dir(test_dir()).read(day) {
+        Ok(input) if input.is_empty() => {
+            eprintln!("skipping: the input of day {day} is empty");
+            None
+        }
+        Ok(input) => Some(input),
+        Err(e) => {
+            eprintln!("skipping: {e}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_file() {
+        let inputs = Inputs::new("does/not/exist");
+        let err = inputs.load(3).unwrap_err();
+        assert!(matches!(err, InputError::Missing { day: 3, .. }));
+        assert!(err.to_string().contains("does/not/exist/day3.txt"));
+    }
+
+    #[test]
+    fn test_override() {
+        let mut inputs = Inputs::new("inputs");
+        inputs.set_override(5, InputSource::from_arg("-"));
+        inputs.set_override(6, InputSource::from_arg("my/day6.txt"));
+        assert_eq!(inputs.source(4), InputSource::Dir(PathBuf::from("inputs")));
+        assert_eq!(inputs.source(5), InputSource::Stdin);
+        assert_eq!(
+            inputs.source(6),
+            InputSource::File(PathBuf::from("my/day6.txt"))
+        );
+
+        assert_eq!(inputs.source(4).name(), "default");
+        assert_eq!(inputs.source(5).name(), "stdin");
+        assert_eq!(inputs.source(6).name(), "day6");
+        assert_eq!(
+            inputs.source(4).path(4),
+            Some(PathBuf::from("inputs/day4.txt"))
+        );
+        assert_eq!(inputs.source(5).path(5), None);
+    }
+
+    #[test]
+    fn test_corpus() {
+        let dir = std::env::temp_dir().join(format!("aoc-corpus-{}", std::process::id()));
+        let inputs = Inputs::new(&dir);
+        std::fs::create_dir_all(inputs.corpus_dir(1)).unwrap();
+        for file in ["bob.txt", "alice.txt", "notes.md"] {
+            std::fs::write(inputs.corpus_dir(1).join(file), "1\n").unwrap();
+        }
+        let corpus = inputs.corpus(1);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let names = corpus
+            .unwrap()
+            .iter()
+            .map(InputSource::name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["alice", "bob"]);
+        assert!(inputs.corpus(2).unwrap().is_empty());
+    }
+}
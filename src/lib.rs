@@ -0,0 +1,55 @@
+//! Advent of Code 2022 solutions, with the goal of fast execution times.
+//!
+//! Every day has its own module with the parse and solve functions of
+//! both parts, e.g. [`day1::part1`]. They take the puzzle input and report
+//! malformed input as a [`parse::ParseError`]:
+//!
+//! ```
+//! assert_eq!(aoc::day1::part1("1000\n2000\n\n3000\n"), Ok(3000));
+//! assert!(aoc::day1::part1("1000\nabc\n").is_err());
+//! ```
+//!
+//! Every day also implements [`solution::Solution`], and the [`registry`]
+//! makes all of them available by number, which is what the runner uses:
+//!
+//! ```
+//! let day7 = aoc::registry::get(7).unwrap();
+//! let parsed = day7.parse("$ cd /\n$ ls\n100 a\n").unwrap();
+//! let part1 = day7.parts()[0];
+//! assert_eq!(parsed.solve(part1), Ok(aoc::answer::Answer::Integer(100)));
+//! ```
+#![cfg_attr(feature = "nightly", feature(portable_simd))]
+#![cfg_attr(all(test, feature = "nightly"), feature(test))]
+
+pub mod allocations;
+pub mod answer;
+pub mod answers;
+pub mod baseline;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod fetch;
+pub mod generate;
+pub mod input;
+pub mod ocr;
+pub mod parse;
+pub mod readme;
+#[cfg(test)]
+mod reference;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
@@ -1,69 +1,478 @@
-#![feature(array_chunks)]
-#![feature(iter_array_chunks)]
-#![feature(portable_simd)]
-#![feature(test)]
-
-use std::fmt::Display;
-
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-#[macro_export]
-macro_rules! run {
-    ($f:expr) => {{
-        _run($f, stringify!($f));
-    }};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc::answer::Answer;
+use aoc::answers::{Answers, ANSWERS_FILE};
+use aoc::baseline::Baseline;
+use aoc::fetch::{Client, Config, FetchError, Fetched, SESSION_ENV};
+use aoc::generate;
+use aoc::input::{InputSource, Inputs};
+use aoc::readme;
+use aoc::registry;
+use aoc::report::{self, Format};
+use aoc::runner::{self, run_day, DayResult, Loaded, Loading, PartResult, Timer};
+use aoc::scaffold;
+use aoc::submit::{self, Outcome, Refusal};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+
+use crate::cli::{
+    BenchArgs, Cli, ClientArgs, Command, Days, FetchArgs, GenArgs, NewArgs, ReadmeArgs, RunArgs,
+    Selection, SubmitArgs, VerifyArgs, FIRST_DAY, LAST_DAY,
+};
+
+mod cli;
+
+fn print_part(day: u8, result: &PartResult) {
+    let Some(answer) = &result.answer else {
+        return;
+    };
+    println!("day{day}::{} = {answer}", result.part.name);
+    if let Answer::Grid { rendered, .. } = answer {
+        println!("{rendered}");
+    }
+}
+
+/// Resolves the selected days and applies the `--input` override.
+fn select(selection: &Selection, inputs: &mut Inputs) -> Days {
+    let days = if selection.all {
+        registry::days().iter().map(|d| d.day()).collect()
+    } else {
+        selection
+            .days
+            .clone()
+            .expect("clap requires days unless --all is given")
+    };
+
+    if let Some(input) = &selection.input {
+        let Some(day) = days.single() else {
+            Cli::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "--input can only be used if a single day is selected",
+                )
+                .exit();
+        };
+        inputs.set_override(day, InputSource::from_arg(input));
+    }
+    days
+}
+
+/// Prints the days that were skipped or could not be loaded, returns the
+/// loaded days and whether all of them could be loaded.
+fn print_loading(loading: Loading) -> (Vec<Loaded>, bool) {
+    for message in loading.skipped.iter().chain(&loading.errors) {
+        eprintln!("{message}");
+    }
+    (loading.loaded, loading.errors.is_empty())
+}
+
+/// Solves the loaded days with up to `jobs` days at the same time, prints
+/// the parts that could not be solved, returns the results and whether all
+/// parts could be solved.
+fn solve(
+    loaded: &[Loaded],
+    part: Option<u8>,
+    timer: Timer,
+    jobs: usize,
+    on_part: impl FnMut(u8, &PartResult),
+) -> (Vec<DayResult>, bool) {
+    let results = runner::run_loaded(loaded, part, timer, jobs, on_part);
+    let errors = runner::errors(&results);
+    for message in &errors {
+        eprintln!("{message}");
+    }
+    (results, errors.is_empty())
+}
+
+/// Prints the wall-clock time of solving all loaded days, see
+/// [`runner::wall_clock`].
+fn print_wall_clock(
+    loaded: &[Loaded],
+    results: &[DayResult],
+    part: Option<u8>,
+    timer: Timer,
+    jobs: usize,
+) {
+    match runner::wall_clock(loaded, results, part, timer, jobs) {
+        Some(time) => println!("{}", report::wall_clock(&time)),
+        None => println!("No day was solved, so there is no wall-clock time"),
+    }
+}
+
+/// Runs the selected days, returns whether all of them could be run.
+fn run(mut inputs: Inputs, args: RunArgs) -> bool {
+    let days = select(&args.selection, &mut inputs);
+    let timer = Timer {
+        warmup: args.warmup,
+        runs: args.runs,
+        timeout: args.timeout,
+    };
+    let (loaded, all_solved) = print_loading(runner::load(days.iter(), &inputs));
+    let part = args.selection.part;
+    let (results, success) = solve(&loaded, part, timer, args.jobs, |day, p| {
+        if args.format == Format::Text {
+            print_part(day, p)
+        }
+    });
+
+    if args.format == Format::Text {
+        if !results.is_empty() {
+            println!();
+            print!("{}", report::render(Format::Text, &results));
+            print_wall_clock(&loaded, &results, part, timer, args.jobs);
+        }
+    } else {
+        print!("{}", report::render(args.format, &results));
+    }
+    success && all_solved
+}
+
+/// Checks the answers of the selected days against the answers file,
+/// returns whether no answer is wrong and all days could be run.
+fn verify(mut inputs: Inputs, answers_file: PathBuf, args: VerifyArgs) -> bool {
+    let mut answers = match Answers::load(&answers_file) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{e}");
+            return false;
+        }
+    };
+    let days = select(&args.selection, &mut inputs);
+    let timer = Timer {
+        warmup: 0,
+        runs: 1,
+        timeout: args.timeout,
+    };
+    let (loaded, all_solved) = print_loading(if args.corpus {
+        runner::load_corpus(days.iter(), &inputs)
+    } else {
+        runner::load(days.iter(), &inputs)
+    });
+    let (mut results, success) = solve(&loaded, args.selection.part, timer, args.jobs, |_, _| ());
+
+    let names = loaded.iter().map(|l| l.source.name()).collect::<Vec<_>>();
+    let verification = answers.verify(&mut results, &names, args.record);
+    for name in &verification.undecodable {
+        eprintln!("{name} cannot be decoded, not recording it");
+    }
+    let mut success = success && all_solved && verification.is_success();
+    if verification.recorded > 0 {
+        if let Err(e) = answers.save(&answers_file) {
+            eprintln!("{e}");
+            success = false;
+        }
+    }
+
+    if args.format == Format::Text {
+        print!("{}", report::verdicts(&results));
+        println!("\n{verification}");
+        if args.corpus {
+            println!("\n{}", report::corpus_summary(&loaded, &results));
+        }
+        if verification.recorded > 0 {
+            println!(
+                "recorded {} answers in {}",
+                verification.recorded,
+                answers_file.display()
+            );
+        }
+    } else {
+        print!("{}", report::render(args.format, &results));
+    }
+    success
+}
+
+/// Benchmarks the selected days, returns whether all of them could be run.
+fn bench(mut inputs: Inputs, args: BenchArgs) -> bool {
+    let days = select(&args.selection, &mut inputs);
+    let timer = Timer {
+        warmup: args.warmup,
+        runs: args.runs,
+        timeout: args.timeout,
+    };
+    let (loaded, all_solved) = print_loading(runner::load(days.iter(), &inputs));
+    let part = args.selection.part;
+    // days measured at the same time compete for the CPU, only the
+    // wall-clock time is measured with `--jobs`
+    let (results, success) = solve(&loaded, part, timer, 1, |_, _| ());
+    let mut success = success && all_solved;
+
+    let text = args.format == Format::Text;
+    if text {
+        print!("{}", report::bench_table(&results));
+        print_wall_clock(&loaded, &results, part, timer, args.jobs);
+        let variants = report::variants_table(&results);
+        if !variants.is_empty() {
+            print!("\nVariants\n{variants}");
+        }
+    } else {
+        print!("{}", report::render(args.format, &results));
+    }
+
+    if let Some(name) = &args.compare {
+        let path = Baseline::path(&args.baseline_dir, name);
+        match Baseline::load(&path) {
+            Ok(baseline) => {
+                let comparisons = baseline.compare(&results, args.threshold);
+                // keep stdout machine-readable for the other formats
+                let table = format!(
+                    "\nCompared with baseline `{name}` (threshold {}%)\n{}",
+                    args.threshold,
+                    report::comparison_table(&comparisons)
+                );
+                if text {
+                    print!("{table}");
+                } else {
+                    eprint!("{table}");
+                }
+                let regressions = comparisons
+                    .iter()
+                    .filter(|c| c.change.is_regression())
+                    .count();
+                if regressions > 0 {
+                    eprintln!("{regressions} parts got slower than baseline `{name}`");
+                    success = false;
+                }
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                success = false;
+            }
+        }
+    }
+
+    if let Some(name) = &args.save {
+        let path = Baseline::path(&args.baseline_dir, name);
+        match Baseline::from_results(&results).save(&path) {
+            Ok(()) => eprintln!("saved baseline `{name}` to {}", path.display()),
+            Err(e) => {
+                eprintln!("{e}");
+                success = false;
+            }
+        }
+    }
+    success
+}
+
+/// Benchmarks every day and rewrites the performance section of the
+/// README, returns whether it was updated.
+fn update_readme(inputs: Inputs, args: ReadmeArgs) -> bool {
+    let days = registry::days().iter().map(|d| d.day());
+    let timer = Timer {
+        warmup: args.warmup,
+        runs: args.runs,
+        timeout: None,
+    };
+    let (loaded, all_solved) = print_loading(runner::load(days, &inputs));
+    let (results, success) = solve(&loaded, None, timer, 1, |_, _| ());
+    if !success || !all_solved {
+        eprintln!(
+            "not updating {} with incomplete results",
+            args.readme.display()
+        );
+        return false;
+    }
+
+    let machine = args.machine.unwrap_or_else(readme::machine);
+    let section = readme::performance_section(&results, timer, &machine);
+    let updated = std::fs::read_to_string(&args.readme)
+        .map_err(|e| e.to_string())
+        .and_then(|content| readme::update(&content, &section).map_err(|e| e.to_string()))
+        .and_then(|updated| std::fs::write(&args.readme, updated).map_err(|e| e.to_string()));
+    match updated {
+        Ok(()) => {
+            print!("{section}");
+            eprintln!("updated {}", args.readme.display());
+            true
+        }
+        Err(e) => {
+            eprintln!("cannot update {}: {e}", args.readme.display());
+            false
+        }
+    }
+}
+
+/// Creates a client from the config, where the session in the environment
+/// and the base URL given on the command line take precedence.
+fn client(inputs: &Inputs, args: ClientArgs) -> Result<Client, FetchError> {
+    let mut config = Config::load(&args.config)?;
+    if let Ok(session) = std::env::var(SESSION_ENV) {
+        config.session = Some(session);
+    }
+    if args.base_url.is_some() {
+        config.base_url = args.base_url;
+    }
+    Ok(Client::new(&config, inputs.dir()))
+}
+
+/// Downloads the inputs of the selected days that are not cached yet,
+/// returns whether all of them are available.
+fn fetch(inputs: Inputs, args: FetchArgs) -> bool {
+    let client = match client(&inputs, args.client) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{e}");
+            return false;
+        }
+    };
+    let days = if args.all {
+        (FIRST_DAY..=LAST_DAY).collect()
+    } else {
+        args.days.expect("clap requires days unless --all is given")
+    };
+
+    let mut success = true;
+    for day in days.iter() {
+        let path = client.path(day);
+        match client.fetch(day) {
+            Ok(Fetched::Cached) => {
+                println!(
+                    "day {day}: {} exists, not fetching it again",
+                    path.display()
+                )
+            }
+            Ok(Fetched::Downloaded(bytes)) => {
+                println!("day {day}: saved {bytes} bytes to {}", path.display())
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                success = false;
+                // every other day would fail the same way
+                if matches!(e, FetchError::MissingSession) {
+                    break;
+                }
+            }
+        }
+    }
+    success
+}
+
+fn submit(mut inputs: Inputs, answers_file: PathBuf, args: SubmitArgs) -> bool {
+    let (day, part) = (args.day, args.part);
+    let Some(solution) = registry::get(day) else {
+        eprintln!("day {day} is not solved yet");
+        return false;
+    };
+    if let Some(input) = &args.input {
+        inputs.set_override(day, InputSource::from_arg(input));
+    }
+    let source = inputs.source(day);
+    let input = match source.read(day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{e}");
+            return false;
+        }
+    };
+    let mut answers = match Answers::load(&answers_file) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{e}");
+            return false;
+        }
+    };
+
+    let timer = Timer {
+        warmup: 0,
+        runs: 1,
+        timeout: None,
+    };
+    let result = run_day(solution, Some(part), &input, timer, |_| ());
+    let result = result
+        .parts
+        .into_iter()
+        .find(|p| p.part.variant().is_none())
+        .expect("every day has both parts");
+    print_part(day, &result);
+    let Some(answer) = result.answer else {
+        eprintln!("{}", result.status.message().unwrap_or("no answer"));
+        return false;
+    };
+
+    let name = source.name();
+    match submit::refusal(&answers, day, &name, part, &answer) {
+        Some(Refusal::KnownRight) => {
+            println!("{}, not submitting it", Refusal::KnownRight);
+            return true;
+        }
+        Some(refusal) => {
+            eprintln!("{refusal}, not submitting it");
+            return false;
+        }
+        None => {}
+    }
+
+    let outcome = match client(&inputs, args.client)
+        .and_then(|client| submit::submit(&client, day, part, &answer))
+    {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("{e}");
+            return false;
+        }
+    };
+    println!("{outcome}");
+    // otherwise nothing was learned about the answer
+    if !submit::record(&mut answers, day, &name, part, answer, &outcome) {
+        return false;
+    }
+    if let Err(e) = answers.save(&answers_file) {
+        eprintln!("{e}");
+        return false;
+    }
+    outcome == Outcome::Correct
+}
+
+fn new_day(inputs: Inputs, args: NewArgs) -> bool {
+    let day = args.day;
+    match scaffold::create(day, args.title.as_deref(), &args.src_dir, inputs.dir()) {
+        Ok(written) => {
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            println!(
+                "day {day} is registered, fill in the example and the parts of {}",
+                args.src_dir.join(format!("day{day}.rs")).display()
+            );
+            true
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            false
+        }
+    }
 }
 
-fn _run<F, O>(f: F, name: &'static str)
-where
-    F: Fn() -> O,
-    O: Display,
-{
-    let result = f();
-    println!("{name} = {result}");
+fn generate(args: GenArgs) -> bool {
+    let Some(generator) = generate::get(args.day) else {
+        eprintln!("day {} has no generator", args.day);
+        return false;
+    };
+    let size = args.size.unwrap_or(generator.size);
+    print!("{}", generator.generate(args.seed, size));
+    true
 }
 
-fn main() {
-    run!(day1::part1);
-    run!(day1::part2);
-    run!(day2::part1);
-    run!(day2::part2);
-    run!(day3::part1);
-    run!(day3::part2);
-    run!(day3::part2_simd);
-    run!(day4::part1);
-    run!(day4::part2);
-    run!(day5::part1);
-    run!(day5::part2);
-    run!(day6::part1);
-    run!(day6::part2);
-    run!(day7::part1);
-    run!(day7::part2);
-    run!(day8::part1);
-    run!(day8::part2);
-    run!(day9::part1);
-    run!(day9::part2);
-    run!(day10::part1);
-    run!(day10::part2);
-    run!(day11::part1);
-    run!(day11::part2);
-    run!(day12::part1);
-    run!(day12::part2);
-    run!(day13::part1);
-    run!(day13::part2);
-    run!(day14::part1);
-    run!(day14::part2);
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let inputs = cli.input_dir.map(Inputs::new).unwrap_or_default();
+    let answers_file = cli
+        .answers
+        .unwrap_or_else(|| inputs.dir().join(ANSWERS_FILE));
+    let success = match cli.command {
+        Command::Run(args) => run(inputs, args),
+        Command::Verify(args) => verify(inputs, answers_file, args),
+        Command::Bench(args) => bench(inputs, args),
+        Command::Readme(args) => update_readme(inputs, args),
+        Command::Fetch(args) => fetch(inputs, args),
+        Command::Submit(args) => submit(inputs, answers_file, args),
+        Command::New(args) => new_day(inputs, args),
+        Command::Gen(args) => generate(args),
+    };
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
 }
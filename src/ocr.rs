@@ -0,0 +1,84 @@
+//! Decodes the block letters that some puzzles render, like the CRT screen of day 10.
+
+const LETTER_WIDTH: usize = 4;
+const LETTER_HEIGHT: usize = 6;
+/// Letters are separated by an empty column.
+const LETTER_SPACING: usize = 1;
+
+#[rustfmt::skip]
+const LETTERS: &[(char, [&str; LETTER_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...", "#...", ".#.#", "..#.", "..#.", "..#."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Decodes a rendered grid of `#` and `.` into text.
+///
+/// Glyphs that aren't known are decoded as `?`.
+pub fn decode(rendered: &str) -> String {
+    let lines = rendered
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(str::as_bytes)
+        .collect::<Vec<_>>();
+    if lines.len() != LETTER_HEIGHT {
+        return String::new();
+    }
+
+    let width = lines.iter().map(|l| l.len()).min().unwrap_or(0);
+    (0..width)
+        .step_by(LETTER_WIDTH + LETTER_SPACING)
+        .filter(|&x| x + LETTER_WIDTH <= width)
+        .map(|x| {
+            LETTERS
+                .iter()
+                .find(|(_, glyph)| {
+                    glyph
+                        .iter()
+                        .zip(&lines)
+                        .all(|(g, l)| g.as_bytes() == &l[x..x + LETTER_WIDTH])
+                })
+                .map_or('?', |(c, _)| *c)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let rendered = "\
+####..##....##.###...##...##..####.#..#.
+#....#..#....#.#..#.#..#.#..#.#....#.#..
+###..#.......#.###..#....#....###..##...
+#....#.##....#.#..#.#.##.#....#....#.#..
+#....#..#.#..#.#..#.#..#.#..#.#....#.#..
+####..###..##..###...###..##..#....#..#.
+";
+        assert_eq!(decode(rendered), "EGJBGCFK");
+    }
+
+    #[test]
+    fn test_decode_unknown() {
+        let rendered = "#...\n.#..\n..#.\n...#\n..#.\n.#..\n";
+        assert_eq!(decode(rendered), "?");
+        assert_eq!(decode("####"), "");
+    }
+}
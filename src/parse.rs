@@ -0,0 +1,243 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Malformed input, with the position where parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Filled in by the registry, the parsers don't know which day they belong to.
+    pub day: Option<u8>,
+    /// 1-based.
+    pub line: usize,
+    /// 1-based, in bytes.
+    pub column: usize,
+    pub expected: String,
+    /// The token at the position, or `end of line` / `end of input`.
+    pub found: String,
+}
+
+impl ParseError {
+    /// Creates an error at the 0-based `line` and `column`.
+    pub fn new(
+        line: usize,
+        column: usize,
+        expected: impl Into<String>,
+        found: impl Into<String>,
+    ) -> Self {
+        Self {
+            day: None,
+            line: line + 1,
+            column: column + 1,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    /// An error for input that ends before `expected`.
+    pub fn end_of_input(input: &str, expected: impl Into<String>) -> Self {
+        Self::new(input.lines().count(), 0, expected, "end of input")
+    }
+
+    pub fn in_day(self, day: u8) -> Self {
+        Self {
+            day: Some(day),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}: ")?;
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The lines of the input as [`Cursor`]s.
+pub fn lines(input: &str) -> impl Iterator<Item = Cursor<'_>> {
+    input.lines().enumerate().map(|(i, l)| Cursor::new(i, l))
+}
+
+/// Reads a single line from left to right and knows where it is, so
+/// that errors point to the right spot.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    line: usize,
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// A cursor at the start of the 0-based `line`.
+    pub fn new(line: usize, text: &'a str) -> Self {
+        Self { line, text, pos: 0 }
+    }
+
+    /// The 0-based line number.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The whole line, regardless of what was read already.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The 0-based column of the next byte.
+    pub fn column(&self) -> usize {
+        self.pos
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.text.len()
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    /// An error at the current position.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        self.error_at(self.pos, expected)
+    }
+
+    /// An error at the 0-based `column`.
+    pub fn error_at(&self, column: usize, expected: impl Into<String>) -> ParseError {
+        let rest = self.text.get(column..).unwrap_or_default();
+        let found = match rest.chars().next() {
+            None => "end of line".to_string(),
+            Some(c) if c.is_ascii_alphanumeric() => {
+                let len = rest
+                    .find(|c: char| !c.is_ascii_alphanumeric())
+                    .unwrap_or(rest.len());
+                format!("`{}`", &rest[..len])
+            }
+            Some(c) => format!("`{c}`"),
+        };
+        ParseError::new(self.line, column, expected, found)
+    }
+
+    /// Skips `literal`.
+    pub fn literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.text[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(format!("`{literal}`")))
+        }
+    }
+
+    /// Reads a number with an optional `-` sign.
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let bytes = &self.text.as_bytes()[self.pos..];
+        let sign = usize::from(bytes.first() == Some(&b'-'));
+        let len = sign
+            + bytes[sign..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+        match self.text[self.pos..self.pos + len].parse() {
+            Ok(n) if len > sign => {
+                self.pos += len;
+                Ok(n)
+            }
+            _ => Err(self.error("a number")),
+        }
+    }
+
+    /// Reads a single byte that must be one of `options`.
+    pub fn one_of(&mut self, options: &[u8]) -> Result<u8, ParseError> {
+        match self.peek() {
+            Some(b) if options.contains(&b) => {
+                self.pos += 1;
+                Ok(b)
+            }
+            _ => {
+                let options = options
+                    .iter()
+                    .map(|&b| format!("`{}`", b as char))
+                    .collect::<Vec<_>>();
+                Err(self.error(format!("one of {}", options.join(", "))))
+            }
+        }
+    }
+
+    /// Reads everything up to the end of the line.
+    pub fn rest(&mut self) -> &'a str {
+        let rest = &self.text[self.pos..];
+        self.pos = self.text.len();
+        rest
+    }
+
+    /// Checks that the whole line was read.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor() {
+        let mut c = Cursor::new(0, "move 12 from -3 to x");
+        c.literal("move ").unwrap();
+        assert_eq!(c.number::<usize>(), Ok(12));
+        c.literal(" from ").unwrap();
+        assert_eq!(c.number::<isize>(), Ok(-3));
+        assert_eq!(c.literal(" at ").unwrap_err().expected, "` at `");
+        c.literal(" to ").unwrap();
+        let e = c.number::<usize>().unwrap_err();
+        assert_eq!((e.line, e.column), (1, 20));
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 20: expected a number, found `x`"
+        );
+        assert_eq!(c.rest(), "x");
+        assert!(c.end().is_ok());
+    }
+
+    #[test]
+    fn test_number() {
+        assert!(Cursor::new(0, "-").number::<isize>().is_err());
+        assert!(Cursor::new(0, "").number::<isize>().is_err());
+        assert!(Cursor::new(0, "-1").number::<usize>().is_err());
+        assert!(Cursor::new(0, "256").number::<u8>().is_err());
+        let mut c = Cursor::new(0, "007,");
+        assert_eq!(c.number::<u8>(), Ok(7));
+        assert_eq!(c.peek(), Some(b','));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut c = Cursor::new(4, "A Q");
+        c.one_of(b"ABC").unwrap();
+        c.literal(" ").unwrap();
+        let e = c.one_of(b"XYZ").unwrap_err().in_day(2);
+        assert_eq!(
+            e.to_string(),
+            "day 2: line 5, column 3: expected one of `X`, `Y`, `Z`, found `Q`"
+        );
+        assert_eq!(Cursor::new(0, "1 2").error_at(1, "a number").found, "` `");
+        assert_eq!(
+            Cursor::new(0, "12").end().map_err(|e| e.found),
+            Err("`12`".to_string())
+        );
+        assert_eq!(
+            ParseError::end_of_input("a\nb\n", "a line").to_string(),
+            "line 3, column 1: expected a line, found end of input"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt::Write;
+use std::fmt::{Display, Formatter};
+
+use crate::report::{self, Format};
+use crate::runner::{DayResult, Timer};
+
+pub const README_FILE: &str = "README.md";
+
+/// The generated part of the performance section is between these lines.
+const START: &str = "<!-- performance:start -->";
+const END: &str = "<!-- performance:end -->";
+
+/// The version of the compiler this binary was built with.
+pub const TOOLCHAIN: &str = env!("AOC_RUSTC_VERSION");
+
+/// Renders the benchmark block of the README with the machine and toolchain
+/// the results were taken with.
+pub fn performance_section(results: &[DayResult], timer: Timer, machine: &str) -> String {
+    let mut section = report::render(Format::Markdown, results);
+    let features = if cfg!(feature = "nightly") {
+        " and the `nightly` feature"
+    } else {
+        ""
+    };
+    let warmup = match timer.warmup {
+        1 => "1 warm-up run".to_string(),
+        n => format!("{n} warm-up runs"),
+    };
+    writeln!(
+        section,
+        "\nThe results posted here were taken on {machine} with `{TOOLCHAIN}`{features}, \
+         as the mean of {} after {warmup}.",
+        report::format_runs(timer.runs as usize),
+    )
+    .unwrap();
+    section
+}
+
+/// Replaces the generated part of the performance section of `readme`.
+pub fn update(readme: &str, section: &str) -> Result<String, ReadmeError> {
+    let start = readme
+        .find(START)
+        .ok_or(ReadmeError::MissingMarker(START))?;
+    let end = readme[start..]
+        .find(END)
+        .map(|end| start + end)
+        .ok_or(ReadmeError::MissingMarker(END))?;
+    let start = start + START.len();
+    Ok(format!("{}\n{section}{}", &readme[..start], &readme[end..]))
+}
+
+/// Describes the CPU, as far as it can be found out.
+pub fn machine() -> String {
+    let cpu = std::fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| {
+            cpuinfo
+                .lines()
+                .find_map(|l| l.strip_prefix("model name")?.split_once(':'))
+                .map(|(_, model)| model.trim().to_string())
+        })
+        .or_else(|| {
+            let output = std::process::Command::new("sysctl")
+                .args(["-n", "machdep.cpu.brand_string"])
+                .output()
+                .ok()?;
+            let model = String::from_utf8(output.stdout).ok()?;
+            Some(model.trim().to_string()).filter(|m| !m.is_empty())
+        });
+    let os = format!("{} {}", std::env::consts::OS, std::env::consts::ARCH);
+    match cpu {
+        Some(cpu) => format!("{cpu} ({os})"),
+        None => os,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadmeError {
+    MissingMarker(&'static str),
+}
+
+impl Display for ReadmeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadmeError::MissingMarker(marker) => {
+                write!(f, "the performance section has no `{marker}` line")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReadmeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use crate::runner::run_day;
+
+    const README: &str = "# AoC
+
+## Performance
+<!-- performance:start -->
+```plain
+test day1::tests::bench_part1 ... bench: 1 ns/iter (+/- 0)
+```
+<!-- performance:end -->
+
+If you want to make something faster, feel free to send a PR my way.
+";
+
+    #[test]
+    fn test_update() {
+        let updated = update(README, "new\n").unwrap();
+        assert_eq!(
+            updated,
+            "# AoC
+
+## Performance
+<!-- performance:start -->
+new
+<!-- performance:end -->
+
+If you want to make something faster, feel free to send a PR my way.
+"
+        );
+        assert_eq!(update(&updated, "new\n").unwrap(), updated);
+        assert_eq!(
+            update("## Performance\n", "new\n"),
+            Err(ReadmeError::MissingMarker(START))
+        );
+        assert_eq!(
+            update(&README.replace(END, ""), "new\n"),
+            Err(ReadmeError::MissingMarker(END))
+        );
+    }
+
+    #[test]
+    fn test_performance_section() {
+        let timer = Timer {
+            warmup: 1,
+            runs: 2,
+            timeout: None,
+        };
+        let results = [
+            run_day(
+                registry::get(1).unwrap(),
+                None,
+                "1\n\n2\n\n3\n",
+                timer,
+                |_| (),
+            ),
+            run_day(registry::get(2).unwrap(), None, "A Y\n", timer, |_| ()),
+        ];
+        let section = performance_section(&results, timer, "a test machine");
+        let lines = section.lines().collect::<Vec<_>>();
+        assert!(lines[1].starts_with("test day1::tests::bench_part1 "));
+        assert!(lines[3].starts_with("test day2::tests::bench_part1 "));
+        assert!(lines[4].starts_with("test day2::tests::bench_part2 "));
+        assert!(lines[7]
+            .starts_with("The results posted here were taken on a test machine with `rustc "));
+        assert!(lines[7].ends_with("as the mean of 2 runs after 1 warm-up run."));
+    }
+}
@@ -0,0 +1,746 @@
+//! Straightforward solutions of every day that the optimized ones are
+//! tested against.
+//!
+//! They follow the puzzle descriptions as literally as possible and don't
+//! care about speed or malformed input, they panic on anything that isn't
+//! a valid puzzle input.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use itertools::Itertools;
+
+use crate::answer::Answer;
+
+/// The answers of both parts of a day, `None` if there is no reference
+/// solution of the day.
+pub fn solve(day: u8, input: &str) -> Option<[Answer; 2]> {
+    let solve = match day {
+        1 => day1,
+        2 => day2,
+        3 => day3,
+        4 => day4,
+        5 => day5,
+        6 => day6,
+        7 => day7,
+        8 => day8,
+        9 => day9,
+        10 => day10,
+        11 => day11,
+        12 => day12,
+        13 => day13,
+        14 => day14,
+        _ => return None,
+    };
+    Some(solve(input))
+}
+
+fn numbers(line: &str) -> Vec<i64> {
+    line.split(|c: char| !c.is_ascii_digit())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().unwrap())
+        .collect()
+}
+
+fn day1(input: &str) -> [Answer; 2] {
+    let mut elves = input
+        .split("\n\n")
+        .map(|elf| elf.lines().map(|l| l.parse::<i64>().unwrap()).sum::<i64>())
+        .collect::<Vec<_>>();
+    elves.sort_unstable_by(|a, b| b.cmp(a));
+    [elves[0].into(), elves[..3].iter().sum::<i64>().into()]
+}
+
+fn day2(input: &str) -> [Answer; 2] {
+    // rock, paper and scissors are 0, 1 and 2, every shape beats the one before it
+    let score = |opponent: i64, me: i64| {
+        let outcome = if me == opponent {
+            3
+        } else if me == (opponent + 1) % 3 {
+            6
+        } else {
+            0
+        };
+        me + 1 + outcome
+    };
+    let rounds = input
+        .lines()
+        .map(|l| {
+            let l = l.as_bytes();
+            (i64::from(l[0] - b'A'), i64::from(l[2] - b'X'))
+        })
+        .collect::<Vec<_>>();
+    let part1 = rounds.iter().map(|&(o, me)| score(o, me)).sum::<i64>();
+    let part2 = rounds
+        .iter()
+        .map(|&(o, outcome)| match outcome {
+            0 => score(o, (o + 2) % 3),
+            1 => score(o, o),
+            _ => score(o, (o + 1) % 3),
+        })
+        .sum::<i64>();
+    [part1.into(), part2.into()]
+}
+
+fn day3(input: &str) -> [Answer; 2] {
+    let priority = |c: char| match c {
+        'a'..='z' => c as i64 - 'a' as i64 + 1,
+        _ => c as i64 - 'A' as i64 + 27,
+    };
+    let rucksacks = input.lines().collect::<Vec<_>>();
+    let part1 = rucksacks
+        .iter()
+        .map(|r| {
+            let (left, right) = r.split_at(r.len() / 2);
+            priority(left.chars().find(|&c| right.contains(c)).unwrap())
+        })
+        .sum::<i64>();
+    let part2 = rucksacks
+        .chunks(3)
+        .map(|group| {
+            let badge = group[0]
+                .chars()
+                .find(|&c| group[1].contains(c) && group[2].contains(c));
+            priority(badge.unwrap())
+        })
+        .sum::<i64>();
+    [part1.into(), part2.into()]
+}
+
+fn day4(input: &str) -> [Answer; 2] {
+    let pairs = input.lines().map(numbers).collect::<Vec<_>>();
+    let contains = |a: &[i64], b: &[i64]| a[0] <= b[0] && b[1] <= a[1];
+    let part1 = pairs
+        .iter()
+        .filter(|p| contains(&p[..2], &p[2..]) || contains(&p[2..], &p[..2]))
+        .count();
+    let part2 = pairs
+        .iter()
+        .filter(|p| p[0] <= p[3] && p[2] <= p[1])
+        .count();
+    [part1.into(), part2.into()]
+}
+
+fn day5(input: &str) -> [Answer; 2] {
+    let (drawing, moves) = input.split_once("\n\n").unwrap();
+    let mut lines = drawing.lines().rev();
+    let count = numbers(lines.next().unwrap()).len();
+    let mut stacks = vec![Vec::new(); count];
+    for line in lines {
+        for (i, stack) in stacks.iter_mut().enumerate() {
+            match line.as_bytes().get(1 + 4 * i) {
+                Some(&c) if c != b' ' => stack.push(c as char),
+                _ => {}
+            }
+        }
+    }
+    let moves = moves.lines().map(numbers).collect::<Vec<_>>();
+    let tops = |stacks: &[Vec<char>]| stacks.iter().map(|s| s.last().unwrap()).collect::<String>();
+
+    let mut part1 = stacks.clone();
+    for m in &moves {
+        for _ in 0..m[0] {
+            let c = part1[m[1] as usize - 1].pop().unwrap();
+            part1[m[2] as usize - 1].push(c);
+        }
+    }
+    let mut part2 = stacks;
+    for m in &moves {
+        let from = &mut part2[m[1] as usize - 1];
+        let crates = from.split_off(from.len() - m[0] as usize);
+        part2[m[2] as usize - 1].extend(crates);
+    }
+    [tops(&part1).into(), tops(&part2).into()]
+}
+
+fn day6(input: &str) -> [Answer; 2] {
+    let stream = input.trim_end().as_bytes();
+    let marker = |len: usize| {
+        let start = stream
+            .windows(len)
+            .position(|w| w.iter().collect::<HashSet<_>>().len() == len)
+            .unwrap();
+        start + len
+    };
+    [marker(4).into(), marker(14).into()]
+}
+
+fn day7(input: &str) -> [Answer; 2] {
+    let mut path = Vec::new();
+    let mut sizes = HashMap::<String, i64>::new();
+    for line in input.lines() {
+        let words = line.split(' ').collect::<Vec<_>>();
+        match words[..] {
+            // a directory that is entered counts even if it is empty
+            ["$", "cd", "/"] => {
+                path.clear();
+                sizes.entry(String::new()).or_default();
+            }
+            ["$", "cd", ".."] => {
+                path.pop();
+            }
+            ["$", "cd", dir] => {
+                path.push(dir);
+                sizes.entry(path.join("/")).or_default();
+            }
+            ["$", "ls"] | ["dir", _] => {}
+            [size, _] => {
+                // the file counts for the directory and all directories above it
+                for depth in 0..=path.len() {
+                    *sizes.entry(path[..depth].join("/")).or_default() +=
+                        size.parse::<i64>().unwrap();
+                }
+            }
+            _ => panic!("unexpected line {line}"),
+        }
+    }
+    let part1 = sizes.values().filter(|&&s| s <= 100_000).sum::<i64>();
+    let needed = (sizes[""] - 40_000_000).max(0);
+    let part2 = sizes.values().filter(|&&s| s >= needed).min().unwrap();
+    [part1.into(), (*part2).into()]
+}
+
+fn day8(input: &str) -> [Answer; 2] {
+    let grid = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+    let (rows, cols) = (grid.len(), grid[0].len());
+    // the trees from a tree to the edge in every direction, nearest first
+    let lines_of_sight = |row: usize, col: usize| {
+        [
+            (0..row).rev().map(|r| grid[r][col]).collect::<Vec<_>>(),
+            (row + 1..rows).map(|r| grid[r][col]).collect(),
+            (0..col).rev().map(|c| grid[row][c]).collect(),
+            (col + 1..cols).map(|c| grid[row][c]).collect(),
+        ]
+    };
+    let trees = (0..rows).cartesian_product(0..cols);
+    let part1 = trees
+        .clone()
+        .filter(|&(row, col)| {
+            let height = grid[row][col];
+            lines_of_sight(row, col)
+                .iter()
+                .any(|trees| trees.iter().all(|&t| t < height))
+        })
+        .count();
+    let part2 = trees
+        .map(|(row, col)| {
+            let height = grid[row][col];
+            lines_of_sight(row, col)
+                .iter()
+                .map(|trees| match trees.iter().position(|&t| t >= height) {
+                    Some(blocking) => blocking + 1,
+                    None => trees.len(),
+                })
+                .product::<usize>()
+        })
+        .max()
+        .unwrap();
+    [part1.into(), part2.into()]
+}
+
+fn day9(input: &str) -> [Answer; 2] {
+    let tail_positions = |knots: usize| {
+        let mut rope = vec![(0_i64, 0_i64); knots];
+        let mut visited = HashSet::from([(0, 0)]);
+        for line in input.lines() {
+            let (direction, steps) = line.split_once(' ').unwrap();
+            let (dx, dy) = match direction {
+                "R" => (1, 0),
+                "L" => (-1, 0),
+                "U" => (0, 1),
+                _ => (0, -1),
+            };
+            for _ in 0..steps.parse::<usize>().unwrap() {
+                rope[0] = (rope[0].0 + dx, rope[0].1 + dy);
+                for i in 1..knots {
+                    let (head, knot) = (rope[i - 1], rope[i]);
+                    if (head.0 - knot.0).abs() > 1 || (head.1 - knot.1).abs() > 1 {
+                        rope[i] = (
+                            knot.0 + (head.0 - knot.0).signum(),
+                            knot.1 + (head.1 - knot.1).signum(),
+                        );
+                    }
+                }
+                visited.insert(rope[knots - 1]);
+            }
+        }
+        visited.len()
+    };
+    [tail_positions(2).into(), tail_positions(10).into()]
+}
+
+fn day10(input: &str) -> [Answer; 2] {
+    // the value of x during every cycle
+    let mut during = Vec::new();
+    let mut x = 1;
+    for line in input.lines() {
+        during.push(x);
+        if let Some(n) = line.strip_prefix("addx ") {
+            during.push(x);
+            x += n.parse::<i64>().unwrap();
+        }
+    }
+    let part1 = [20, 60, 100, 140, 180, 220]
+        .iter()
+        .filter(|&&cycle| cycle <= during.len())
+        .map(|&cycle| cycle as i64 * during[cycle - 1])
+        .sum::<i64>();
+    let mut screen = String::new();
+    for row in 0..6 {
+        for col in 0..40 {
+            let lit = matches!(during.get(row * 40 + col), Some(x) if (x - col as i64).abs() <= 1);
+            screen.push(if lit { '#' } else { '.' });
+        }
+        screen.push('\n');
+    }
+    [part1.into(), Answer::grid(screen)]
+}
+
+fn day11(input: &str) -> [Answer; 2] {
+    struct Monkey {
+        items: Vec<u64>,
+        operation: Vec<String>,
+        divisor: u64,
+        targets: [usize; 2],
+    }
+    let monkeys = input
+        .split("\n\n")
+        .map(|monkey| {
+            let lines = monkey.lines().collect::<Vec<_>>();
+            let last_number = |line: &str| line.rsplit(' ').next().unwrap().parse().unwrap();
+            Monkey {
+                items: numbers(lines[1]).into_iter().map(|n| n as u64).collect(),
+                operation: lines[2]
+                    .split(' ')
+                    .rev()
+                    .take(2)
+                    .map(String::from)
+                    .collect(),
+                divisor: last_number(lines[3]),
+                targets: [
+                    last_number(lines[4]) as usize,
+                    last_number(lines[5]) as usize,
+                ],
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let business = |rounds: usize, relief: &dyn Fn(u64) -> u64| {
+        let mut items = monkeys.iter().map(|m| m.items.clone()).collect::<Vec<_>>();
+        let mut inspections = vec![0_usize; monkeys.len()];
+        for _ in 0..rounds {
+            for (i, monkey) in monkeys.iter().enumerate() {
+                for item in std::mem::take(&mut items[i]) {
+                    inspections[i] += 1;
+                    let operand = match monkey.operation[0].as_str() {
+                        "old" => item,
+                        n => n.parse().unwrap(),
+                    };
+                    let item = match monkey.operation[1].as_str() {
+                        "+" => item + operand,
+                        _ => item * operand,
+                    };
+                    let item = relief(item);
+                    let target = monkey.targets[usize::from(!item.is_multiple_of(monkey.divisor))];
+                    items[target].push(item);
+                }
+            }
+        }
+        inspections.sort_unstable_by(|a, b| b.cmp(a));
+        inspections[0] * inspections[1]
+    };
+    let modulus = monkeys.iter().map(|m| m.divisor).product::<u64>();
+    [
+        business(20, &|item| item / 3).into(),
+        business(10_000, &|item| item % modulus).into(),
+    ]
+}
+
+fn day12(input: &str) -> [Answer; 2] {
+    let map = &input.lines().map(str::as_bytes).collect::<Vec<_>>();
+    let find = |target: u8| {
+        (0..map.len())
+            .cartesian_product(0..map[0].len())
+            .filter(move |&(row, col)| map[row][col] == target)
+    };
+    let elevation = |(row, col): (usize, usize)| match map[row][col] {
+        b'S' => b'a',
+        b'E' => b'z',
+        c => c,
+    };
+    let end = find(b'E').next().unwrap();
+    // every step goes up by at most one
+    let steps = |starts: Vec<(usize, usize)>| {
+        let mut distances = starts
+            .iter()
+            .map(|&s| (s, 0_usize))
+            .collect::<HashMap<_, _>>();
+        let mut queue = VecDeque::from(starts);
+        while let Some(p) = queue.pop_front() {
+            if p == end {
+                return distances[&p];
+            }
+            let (row, col) = p;
+            let neighbours = [
+                (row.wrapping_sub(1), col),
+                (row + 1, col),
+                (row, col.wrapping_sub(1)),
+                (row, col + 1),
+            ];
+            for n in neighbours {
+                if n.0 < map.len()
+                    && n.1 < map[0].len()
+                    && elevation(n) <= elevation(p) + 1
+                    && !distances.contains_key(&n)
+                {
+                    distances.insert(n, distances[&p] + 1);
+                    queue.push_back(n);
+                }
+            }
+        }
+        panic!("the end cannot be reached")
+    };
+    let part1 = steps(find(b'S').collect());
+    let part2 = steps(find(b'S').chain(find(b'a')).collect());
+    [part1.into(), part2.into()]
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Packet {
+    List(Vec<Packet>),
+    Number(u32),
+}
+
+impl Packet {
+    fn parse(s: &str) -> Packet {
+        fn parse(s: &[u8], i: &mut usize) -> Packet {
+            if s[*i] != b'[' {
+                let start = *i;
+                while s[*i].is_ascii_digit() {
+                    *i += 1;
+                }
+                let number = std::str::from_utf8(&s[start..*i]).unwrap();
+                return Packet::Number(number.parse().unwrap());
+            }
+            *i += 1;
+            let mut list = Vec::new();
+            while s[*i] != b']' {
+                list.push(parse(s, i));
+                if s[*i] == b',' {
+                    *i += 1;
+                }
+            }
+            *i += 1;
+            Packet::List(list)
+        }
+        parse(s.as_bytes(), &mut 0)
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Number(a), Packet::Number(b)) => a.cmp(b),
+            (Packet::List(a), Packet::List(b)) => a.cmp(b),
+            (Packet::Number(a), b) => Packet::List(vec![Packet::Number(*a)]).cmp(b),
+            (a, Packet::Number(b)) => a.cmp(&Packet::List(vec![Packet::Number(*b)])),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn day13(input: &str) -> [Answer; 2] {
+    let packets = input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(Packet::parse)
+        .collect::<Vec<_>>();
+    let part1 = packets
+        .chunks(2)
+        .enumerate()
+        // real inputs have no equal pairs, they count as ordered like in the solution
+        .filter(|(_, pair)| pair[0] <= pair[1])
+        .map(|(i, _)| i + 1)
+        .sum::<usize>();
+    // packets that equal a divider come before it, like in the solution
+    let dividers = [Packet::parse("[[2]]"), Packet::parse("[[6]]")];
+    let mut sorted = packets
+        .into_iter()
+        .map(|p| (p, false))
+        .chain(dividers.map(|d| (d, true)))
+        .collect::<Vec<_>>();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+    let part2 = sorted
+        .iter()
+        .positions(|&(_, divider)| divider)
+        .map(|i| i + 1)
+        .product::<usize>();
+    [part1.into(), part2.into()]
+}
+
+fn day14(input: &str) -> [Answer; 2] {
+    let paths = input
+        .lines()
+        .map(|line| {
+            line.split(" -> ")
+                .map(|p| {
+                    let (x, y) = p.split_once(',').unwrap();
+                    (x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap())
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let lowest = paths.iter().flatten().map(|&(_, y)| y).max().unwrap();
+    let rightmost = paths.iter().flatten().map(|&(x, _)| x).max().unwrap();
+    // with the floor, sand spreads at most one column per row to each side
+    let mut rocks = vec![vec![false; rightmost.max(500 + lowest) + 3]; lowest + 2];
+    for pair in paths.iter().flat_map(|path| path.windows(2)) {
+        let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+        for row in &mut rocks[y1.min(y2)..=y1.max(y2)] {
+            row[x1.min(x2)..=x1.max(x2)].fill(true);
+        }
+    }
+
+    // the units of sand that come to rest, with the floor of part 2 or
+    // until the sand falls below the lowest rock
+    let resting = |floor: bool| {
+        let mut blocked = rocks.clone();
+        let mut count = 0_usize;
+        'pour: while !blocked[0][500] {
+            let (mut x, mut y) = (500, 0);
+            loop {
+                if y == lowest + 1 {
+                    match floor {
+                        true => break,
+                        false => break 'pour,
+                    }
+                }
+                let next = [x, x - 1, x + 1].into_iter().find(|&x| !blocked[y + 1][x]);
+                match next {
+                    Some(next) => (x, y) = (next, y + 1),
+                    None => break,
+                }
+            }
+            blocked[y][x] = true;
+            count += 1;
+        }
+        count
+    };
+    [resting(false).into(), resting(true).into()]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::generate::generators;
+    use crate::parse::ParseError;
+    use crate::registry;
+    use crate::runner::quietly;
+
+    /// The seeds that are tried for inputs of about the size of a real
+    /// input, these take much longer to solve.
+    const LARGE_SEEDS: u64 = 2;
+
+    /// The seeds that are tried for every small size, days 11 and 14 take
+    /// long to solve even for small inputs.
+    fn seeds(day: u8) -> u64 {
+        match day {
+            11 | 14 => 8,
+            _ => 64,
+        }
+    }
+
+    /// An input where a part disagrees with the reference solution.
+    struct Disagreement {
+        seed: u64,
+        size: usize,
+        input: String,
+        part: &'static str,
+        /// The answer of the part, or how it failed.
+        solution: Result<String, String>,
+        reference: String,
+    }
+
+    /// How an answer is compared, grids by their pixels rather than by the
+    /// letters they decode to.
+    fn compared(answer: &Answer) -> String {
+        match answer {
+            Answer::Grid { rendered, .. } => rendered.clone(),
+            answer => answer.to_string(),
+        }
+    }
+
+    /// The result of a step that ran [`quietly`], or how it failed.
+    fn outcome<T>(result: Result<Result<T, ParseError>, String>) -> Result<T, String> {
+        match result {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(e)) => Err(format!("an error: {e}")),
+            Err(message) => Err(format!("a panic: {message}")),
+        }
+    }
+
+    /// The first part of a day, variants included, that disagrees with the
+    /// reference solution on an input, with its answer and the reference
+    /// answer. A part that returns an error or panics disagrees too, its
+    /// answer is then the `Err` describing that. `None` if they agree or if
+    /// the reference cannot solve the input.
+    fn disagreement(
+        day: u8,
+        input: &str,
+    ) -> Option<(&'static str, Result<String, String>, String)> {
+        // shrinking an input breaks it more often than not
+        let reference = quietly(|| solve(day, input).unwrap()).ok()?;
+        let day = registry::get(day).unwrap();
+        let parsed = outcome(quietly(|| day.parse(input)));
+        day.parts().into_iter().find_map(|part| {
+            let answer = parsed.as_ref().map_err(Clone::clone).and_then(|parsed| {
+                outcome(quietly(|| parsed.solve(part))).map(|answer| compared(&answer))
+            });
+            let expected = compared(&reference[usize::from(part.part) - 1]);
+            (answer.as_ref() != Ok(&expected)).then_some((part.name, answer, expected))
+        })
+    }
+
+    /// Removes as many lines from an input as possible while `part` still
+    /// disagrees in the same way, answering or failing, trying to remove
+    /// runs of lines from long to short. Lines the reference cannot do
+    /// without are kept.
+    fn shrink(day: u8, input: &str, part: &str, answered: bool) -> String {
+        let mut lines = input.split_inclusive('\n').collect::<Vec<_>>();
+        let mut len = lines.len() / 2;
+        while len > 0 {
+            let shrunk = (0..=lines.len() - len).find_map(|start| {
+                let mut shrunk = lines.clone();
+                shrunk.drain(start..start + len);
+                let disagrees = disagreement(day, &shrunk.concat())
+                    .is_some_and(|(p, answer, _)| p == part && answer.is_ok() == answered);
+                disagrees.then_some(shrunk)
+            });
+            match shrunk {
+                Some(shrunk) => {
+                    lines = shrunk;
+                    len = len.min(lines.len());
+                }
+                None => len -= 1,
+            }
+        }
+        lines.concat()
+    }
+
+    /// Tries generated inputs from the smallest size up, with the given
+    /// number of seeds for every size, and shrinks the shortest input of
+    /// the smallest size where a part disagrees.
+    fn smallest_disagreement(
+        day: u8,
+        sizes: impl IntoIterator<Item = (usize, u64)>,
+    ) -> Option<Disagreement> {
+        let generator = crate::generate::get(day).unwrap();
+        let (seed, size, input, part) = sizes.into_iter().find_map(|(size, seeds)| {
+            (0..seeds)
+                .filter_map(|seed| {
+                    let input = generator.generate(seed, size);
+                    let (part, answer, _) = disagreement(day, &input)?;
+                    Some((seed, size, input, (part, answer.is_ok())))
+                })
+                .min_by_key(|(.., input, _)| input.len())
+        })?;
+        let input = shrink(day, &input, part.0, part.1);
+        let (part, solution, reference) = disagreement(day, &input).unwrap();
+        Some(Disagreement {
+            seed,
+            size,
+            input,
+            part,
+            solution,
+            reference,
+        })
+    }
+
+    /// The optimized solutions agree with the reference solutions on
+    /// generated inputs, from tiny ones up to beyond the size of a real
+    /// input. The days are checked in parallel, some take a while in debug
+    /// builds.
+    #[test]
+    fn test_against_reference() {
+        thread::scope(|scope| {
+            let checks = generators()
+                .iter()
+                .map(|generator| {
+                    let sizes = [1, 2, 3, 5, 8]
+                        .map(|size| (size, seeds(generator.day)))
+                        .into_iter()
+                        .chain([generator.size / 4, generator.size].map(|size| (size, LARGE_SEEDS)))
+                        .chain([(generator.size * 3, 1)]);
+                    let check = scope.spawn(move || smallest_disagreement(generator.day, sizes));
+                    (generator.day, check)
+                })
+                .collect::<Vec<_>>();
+            for (day, check) in checks {
+                if let Some(d) = check.join().unwrap() {
+                    let outcome = match d.solution {
+                        Ok(answer) => format!("answered {answer} instead of {}", d.reference),
+                        Err(failure) => {
+                            format!("failed with {failure} instead of answering {}", d.reference)
+                        }
+                    };
+                    panic!(
+                        "day {day} {} {outcome} for this input, shrunk from seed {} and size \
+                         {}:\n{}",
+                        d.part, d.seed, d.size, d.input
+                    );
+                }
+            }
+        });
+    }
+
+    /// A part that fails on an input the reference solves disagrees, an
+    /// input the reference cannot solve is left out.
+    #[test]
+    fn test_disagreement() {
+        assert_eq!(disagreement(2, "A Y\nB X\nC Z\n"), None);
+        // the reference ignores the extra shape
+        let (part, answer, expected) = disagreement(2, "A YZ\n").unwrap();
+        assert_eq!((part, expected.as_str()), ("part1", "8"));
+        assert!(answer.unwrap_err().starts_with("an error: "));
+        assert_eq!(disagreement(9, "R\n"), None);
+    }
+
+    #[test]
+    fn test_examples() {
+        let examples: [(u8, &str, [Answer; 2]); 4] = [
+            (
+                1,
+                "1000\n2000\n\n3000\n\n4000\n\n5000\n6000\n",
+                [Answer::Integer(11000), Answer::Integer(18000)],
+            ),
+            (
+                2,
+                "A Y\nB X\nC Z\n",
+                [Answer::Integer(15), Answer::Integer(12)],
+            ),
+            (
+                6,
+                "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n",
+                [Answer::Integer(7), Answer::Integer(19)],
+            ),
+            (
+                13,
+                "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n[[1],4]\n\n[9]\n[[8,7,6]]\n\n\
+                 [[4,4],4,4]\n[[4,4],4,4,4]\n\n[7,7,7,7]\n[7,7,7]\n\n[]\n[3]\n\n\
+                 [[[]]]\n[[]]\n\n[1,[2,[3,[4,[5,6,7]]]],8,9]\n[1,[2,[3,[4,[5,6,0]]]],8,9]\n",
+                [Answer::Integer(13), Answer::Integer(140)],
+            ),
+        ];
+        for (day, input, expected) in examples {
+            assert_eq!(solve(day, input), Some(expected), "day {day}");
+        }
+        assert_eq!(solve(25, ""), None);
+    }
+}
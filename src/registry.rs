@@ -0,0 +1,243 @@
+use std::marker::PhantomData;
+
+use crate::answer::Answer;
+use crate::parse::ParseError;
+use crate::solution::Solution;
+use crate::{
+    day1, day10, day11, day12, day13, day14, day2, day3, day4, day5, day6, day7, day8, day9,
+};
+
+/// All solved days, ordered by day. Adding a day only requires adding it here.
+static DAYS: &[&dyn Day] = &[
+    &Registered::<day1::Day1>::new(),
+    &Registered::<day2::Day2>::new(),
+    &Registered::<day3::Day3>::new(),
+    &Registered::<day4::Day4>::new(),
+    &Registered::<day5::Day5>::new(),
+    &Registered::<day6::Day6>::new(),
+    &Registered::<day7::Day7>::new(),
+    &Registered::<day8::Day8>::new(),
+    &Registered::<day9::Day9>::new(),
+    &Registered::<day10::Day10>::new(),
+    &Registered::<day11::Day11>::new(),
+    &Registered::<day12::Day12>::new(),
+    &Registered::<day13::Day13>::new(),
+    &Registered::<day14::Day14>::new(),
+];
+
+pub fn days() -> &'static [&'static dyn Day] {
+    DAYS
+}
+
+pub fn get(day: u8) -> Option<&'static dyn Day> {
+    DAYS.iter().copied().find(|d| d.day() == day)
+}
+
+/// Identifies one implementation of a part of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartId {
+    pub part: u8,
+    /// `part1`, `part2` or the name of a variant, e.g. `part2_simd`.
+    pub name: &'static str,
+}
+
+impl PartId {
+    /// The name of an alternative implementation, e.g. `simd` for `part2_simd`.
+    pub fn variant(&self) -> Option<&'static str> {
+        self.name.split_once('_').map(|(_, variant)| variant)
+    }
+}
+
+/// A type erased [`Solution`].
+pub trait Day: Sync {
+    fn day(&self) -> u8;
+
+    /// All implementations of both parts, ordered by part.
+    fn parts(&self) -> Vec<PartId>;
+
+    fn has_parse_step(&self) -> bool;
+
+    fn parse_bench(&self) -> Option<&'static str>;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, ParseError>;
+}
+
+/// The parsed input of a [`Day`].
+pub trait Parsed {
+    fn solve(&self, part: PartId) -> Result<Answer, ParseError>;
+
+    /// The answers of the main implementations of both parts.
+    fn solve_both(&self) -> Result<(Answer, Answer), ParseError>;
+}
+
+pub(crate) struct Registered<S>(PhantomData<fn() -> S>);
+
+impl<S> Registered<S> {
+    pub(crate) const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: Solution + 'static> Day for Registered<S> {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parts(&self) -> Vec<PartId> {
+        let mut parts = vec![
+            PartId {
+                part: 1,
+                name: "part1",
+            },
+            PartId {
+                part: 2,
+                name: "part2",
+            },
+        ];
+        parts.extend(S::variants().into_iter().map(|v| PartId {
+            part: v.part,
+            name: v.name,
+        }));
+        parts.sort_by_key(|p| p.part);
+        parts
+    }
+
+    fn has_parse_step(&self) -> bool {
+        S::HAS_PARSE_STEP
+    }
+
+    fn parse_bench(&self) -> Option<&'static str> {
+        S::PARSE_BENCH
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, ParseError> {
+        match S::parse(input) {
+            Ok(parsed) => Ok(Box::new(ParsedInput::<S>(parsed))),
+            Err(e) => Err(e.in_day(S::DAY)),
+        }
+    }
+}
+
+struct ParsedInput<'a, S: Solution>(S::Parsed<'a>);
+
+impl<S: Solution> Parsed for ParsedInput<'_, S> {
+    fn solve(&self, part: PartId) -> Result<Answer, ParseError> {
+        let answer = match part.name {
+            "part1" => S::part1(&self.0),
+            "part2" => S::part2(&self.0),
+            name => {
+                let variant = S::variants()
+                    .into_iter()
+                    .find(|v| v.name == name)
+                    .unwrap_or_else(|| panic!("day {} has no part {name}", S::DAY));
+                (variant.run)(&self.0)
+            }
+        };
+        answer.map_err(|e| e.in_day(S::DAY))
+    }
+
+    fn solve_both(&self) -> Result<(Answer, Answer), ParseError> {
+        S::solve(&self.0).map_err(|e| e.in_day(S::DAY))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::{Answers, Verdict, ANSWERS_FILE};
+    use crate::input::{test_dir, test_input, Inputs};
+
+    #[test]
+    fn test_days_are_ordered_and_unique() {
+        assert!(days().windows(2).all(|w| w[0].day() < w[1].day()));
+        assert_eq!(get(7).map(|d| d.day()), Some(7));
+        assert!(get(25).is_none());
+    }
+
+    #[test]
+    fn test_solve_both() {
+        let parsed = get(7).unwrap().parse("$ cd /\n40000001 a\n").unwrap();
+        assert_eq!(
+            parsed.solve_both(),
+            Ok((Answer::Integer(0), Answer::Integer(40000001)))
+        );
+
+        for day in days() {
+            let Some(input) = test_input(day.day()) else {
+                continue;
+            };
+            let parsed = day.parse(&input).unwrap();
+            let [part1, part2] = [1, 2].map(|part| {
+                let main = day.parts().into_iter().find(|p| p.part == part).unwrap();
+                parsed.solve(main).unwrap()
+            });
+            assert_eq!(parsed.solve_both(), Ok((part1, part2)), "day {}", day.day());
+        }
+    }
+
+    #[test]
+    fn test_variants_agree() {
+        for day in days() {
+            let Some(input) = test_input(day.day()) else {
+                continue;
+            };
+            let parsed = day.parse(&input).unwrap();
+            let parts = day.parts();
+            for part in &parts {
+                let main = parts
+                    .iter()
+                    .find(|p| p.part == part.part)
+                    .expect("every part has a main implementation");
+                assert_eq!(
+                    parsed.solve(*part),
+                    parsed.solve(*main),
+                    "day{}::{}",
+                    day.day(),
+                    part.name
+                );
+            }
+        }
+    }
+
+    /// Solves every input in the corpus and lists every file with a wrong
+    /// answer or an error at once.
+    #[test]
+    fn test_corpus() {
+        let inputs = Inputs::new(test_dir());
+        let answers = Answers::load(&test_dir().join(ANSWERS_FILE)).unwrap();
+        let mut mismatches = Vec::new();
+        for day in days() {
+            for source in inputs.corpus(day.day()).unwrap() {
+                let path = source.path(day.day()).unwrap_or_default();
+                let solved = source
+                    .read(day.day())
+                    .map_err(|e| e.to_string())
+                    .and_then(|input| {
+                        let parsed = day.parse(&input).map_err(|e| e.to_string())?;
+                        day.parts()
+                            .into_iter()
+                            .map(|p| Ok((p, parsed.solve(p).map_err(|e| e.to_string())?)))
+                            .collect::<Result<Vec<_>, String>>()
+                    });
+                let solved = match solved {
+                    Ok(solved) => solved,
+                    Err(e) => {
+                        mismatches.push(format!("{}: {e}", path.display()));
+                        continue;
+                    }
+                };
+                for (part, answer) in solved {
+                    let verdict = answers.check(day.day(), &source.name(), part.part, &answer);
+                    if let Verdict::Fail { expected } = verdict {
+                        mismatches.push(format!(
+                            "{}: {} = {answer}, expected {expected}",
+                            path.display(),
+                            part.name
+                        ));
+                    }
+                }
+            }
+        }
+        assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+    }
+}
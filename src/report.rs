@@ -0,0 +1,934 @@
+use std::fmt::Write;
+use std::iter;
+use std::time::Duration;
+
+use clap::ValueEnum;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::allocations::Allocations;
+use crate::answer::Answer;
+use crate::answers::Verdict;
+use crate::baseline::Comparison;
+use crate::registry;
+use crate::runner::{DayResult, Loaded, Measurement, PartResult, Status, WallClock};
+
+/// How the results of a run are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    /// Answers as they are solved, followed by a summary table.
+    #[default]
+    Text,
+    Json,
+    Csv,
+    /// The benchmark block that is used in the README.
+    Markdown,
+}
+
+/// Renders the results in the given format.
+///
+/// The answers of [`Format::Text`] are printed while running, so this only
+/// renders the summary table for it.
+pub fn render(format: Format, results: &[DayResult]) -> String {
+    match format {
+        Format::Text => summary_table(results),
+        Format::Json => json(results),
+        Format::Csv => csv(results),
+        Format::Markdown => markdown(results),
+    }
+}
+
+/// A single executed part, flattened for the machine-readable formats.
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    day: u8,
+    part: u8,
+    variant: Option<&'a str>,
+    answer: Option<&'a Answer>,
+    status: &'a str,
+    message: Option<&'a str>,
+    /// Only set for the first part of a day with a distinct parse step.
+    parse_ns: Option<u128>,
+    mean_ns: Option<u128>,
+    min_ns: Option<u128>,
+    median_ns: Option<u128>,
+    p95_ns: Option<u128>,
+    max_ns: Option<u128>,
+    runs: Option<usize>,
+    input_bytes: usize,
+    /// Only set with the `count-allocations` feature.
+    allocations: Option<usize>,
+    allocated_bytes: Option<usize>,
+    peak_bytes: Option<usize>,
+    verdict: Option<&'a str>,
+    expected: Option<&'a Answer>,
+    /// Only set if a day is run on more than one input.
+    input: Option<&'a str>,
+}
+
+impl<'a> Record<'a> {
+    fn new(day: &'a DayResult, index: usize, part: &'a PartResult) -> Self {
+        Self {
+            day: day.day,
+            part: part.part.part,
+            variant: part.part.variant(),
+            answer: part.answer.as_ref(),
+            status: part.status.as_str(),
+            message: part.status.message(),
+            parse_ns: day
+                .parse
+                .as_ref()
+                .filter(|_| index == 0)
+                .map(|p| p.mean().as_nanos()),
+            mean_ns: part.time.as_ref().map(|t| t.mean().as_nanos()),
+            min_ns: part.time.as_ref().map(|t| t.min().as_nanos()),
+            median_ns: part.time.as_ref().map(|t| t.median().as_nanos()),
+            p95_ns: part.time.as_ref().map(|t| t.percentile(95).as_nanos()),
+            max_ns: part.time.as_ref().map(|t| t.max().as_nanos()),
+            runs: part.time.as_ref().map(Measurement::runs),
+            input_bytes: day.input_len,
+            allocations: allocations(part).map(|a| a.count),
+            allocated_bytes: allocations(part).map(|a| a.bytes),
+            peak_bytes: allocations(part).map(|a| a.peak),
+            verdict: part.verdict.as_ref().map(Verdict::as_str),
+            expected: match &part.verdict {
+                Some(Verdict::Fail { expected }) => Some(expected),
+                _ => None,
+            },
+            input: day.input.as_deref(),
+        }
+    }
+}
+
+fn allocations(part: &PartResult) -> Option<Allocations> {
+    part.time.as_ref()?.allocations()
+}
+
+fn records(results: &[DayResult]) -> impl Iterator<Item = Record<'_>> {
+    results.iter().flat_map(|day| {
+        day.parts
+            .iter()
+            .enumerate()
+            .map(move |(i, part)| Record::new(day, i, part))
+    })
+}
+
+fn json(results: &[DayResult]) -> String {
+    let records = records(results).collect::<Vec<_>>();
+    let mut json = serde_json::to_string_pretty(&records).expect("records are serializable");
+    json.push('\n');
+    json
+}
+
+fn csv(results: &[DayResult]) -> String {
+    fn field<T: ToString>(v: Option<T>) -> String {
+        let v = v.map(|v| v.to_string()).unwrap_or_default();
+        if v.contains([',', '"', '\n']) {
+            format!("\"{}\"", v.replace('"', "\"\""))
+        } else {
+            v
+        }
+    }
+
+    let mut csv =
+        "day,part,variant,answer,status,message,parse_ns,mean_ns,min_ns,median_ns,p95_ns,max_ns,runs,input_bytes,allocations,allocated_bytes,peak_bytes,verdict,expected,input\n".to_string();
+    for r in records(results) {
+        let fields = [
+            r.day.to_string(),
+            r.part.to_string(),
+            field(r.variant),
+            field(r.answer),
+            r.status.to_string(),
+            field(r.message),
+            field(r.parse_ns),
+            field(r.mean_ns),
+            field(r.min_ns),
+            field(r.median_ns),
+            field(r.p95_ns),
+            field(r.max_ns),
+            field(r.runs),
+            r.input_bytes.to_string(),
+            field(r.allocations),
+            field(r.allocated_bytes),
+            field(r.peak_bytes),
+            field(r.verdict),
+            field(r.expected),
+            field(r.input),
+        ];
+        writeln!(csv, "{}", fields.join(",")).unwrap();
+    }
+    csv
+}
+
+/// Renders the results like the output of `cargo bench`, which is how the
+/// performance section of the README has always looked. The parse step
+/// only gets a row on days that have a benchmark for it.
+fn markdown(results: &[DayResult]) -> String {
+    let mut lines = Vec::new();
+    for day in results {
+        let bench = registry::get(day.day).and_then(|d| d.parse_bench());
+        if let (Some(parse), Some(bench)) = (&day.parse, bench) {
+            lines.push((format!("day{}::tests::{bench}", day.day), parse));
+        }
+        for part in &day.parts {
+            if let Some(time) = &part.time {
+                let name = format!("day{}::tests::bench_{}", day.day, part.part.name);
+                lines.push((name, time));
+            }
+        }
+    }
+
+    let width = lines.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let mut markdown = "```plain\n".to_string();
+    for (name, time) in lines {
+        writeln!(
+            markdown,
+            "test {name:<width$} ... bench: {:>11} ns/iter (+/- {})",
+            thousands(time.mean().as_nanos()),
+            thousands((time.max() - time.min()).as_nanos()),
+        )
+        .unwrap();
+    }
+    markdown.push_str("```\n");
+    markdown
+}
+
+/// Formats a number with `,` as thousands separator, like `cargo bench` does.
+fn thousands(n: u128) -> String {
+    let digits = n.to_string();
+    let mut s = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            s.push(',');
+        }
+        s.push(c);
+    }
+    s
+}
+
+/// Renders a table with the answer and times of every part and of solving
+/// both parts at once, and the totals of all days.
+pub fn summary_table(results: &[DayResult]) -> String {
+    let allocations_header = allocations_header(results);
+    let headers = [
+        "Day",
+        "Part",
+        "Answer",
+        "Parse",
+        "Mean",
+        "Min",
+        "Max",
+        &allocations_header,
+    ];
+    let mut rows = Vec::new();
+    for day in results {
+        for (i, part) in day.parts.iter().enumerate() {
+            // the parse time belongs to the day, so only show it once
+            let parse = day.parse.as_ref().filter(|_| i == 0);
+            let time = |f: fn(&Measurement) -> Duration| {
+                part.time
+                    .as_ref()
+                    .map_or("-".to_string(), |t| format_duration(f(t)))
+            };
+            rows.push(vec![
+                day.day.to_string(),
+                part.part.name.to_string(),
+                format_answer(part),
+                parse.map_or("-".to_string(), |p| format_duration(p.mean())),
+                time(Measurement::mean),
+                time(Measurement::min),
+                time(Measurement::max),
+                format_allocations(allocations(part)),
+            ]);
+        }
+        if let Some(both) = &day.both {
+            rows.push(vec![
+                day.day.to_string(),
+                "both".to_string(),
+                String::new(),
+                "-".to_string(),
+                format_duration(both.mean()),
+                format_duration(both.min()),
+                format_duration(both.max()),
+                format_allocations(both.allocations()),
+            ]);
+        }
+    }
+    let parse_total = results
+        .iter()
+        .filter_map(|d| d.parse.as_ref().map(Measurement::mean))
+        .sum::<Duration>();
+    let solve_total = results.iter().map(DayResult::solve_total).sum();
+    let total = results.iter().map(DayResult::total).sum::<Duration>();
+    // parts that were not solved have no time
+    let runs = results
+        .iter()
+        .flat_map(|d| d.parts.iter().filter_map(|p| p.time.as_ref()))
+        .map(Measurement::runs)
+        .max();
+    let parse_runs = results
+        .iter()
+        .filter_map(|d| d.parse.as_ref().map(Measurement::runs))
+        .max();
+    rows.push(Vec::new());
+    rows.push(vec![
+        "Total".to_string(),
+        String::new(),
+        String::new(),
+        parse_runs.map_or("-".to_string(), |_| format_duration(parse_total)),
+        runs.map_or("-".to_string(), |_| format_duration(solve_total)),
+    ]);
+    let mut table = table(&headers, &rows);
+
+    match (runs, parse_runs) {
+        (Some(runs), _) => writeln!(
+            table,
+            "\nParsing and solving took {} (mean of {} per part)",
+            format_duration(total),
+            format_runs(runs)
+        ),
+        (None, Some(runs)) => writeln!(
+            table,
+            "\nParsing took {} (mean of {}), no part was solved",
+            format_duration(parse_total),
+            format_runs(runs)
+        ),
+        (None, None) => Ok(()),
+    }
+    .unwrap();
+    table
+}
+
+/// Renders a table with the distribution of the times of parsing, of every
+/// part and of solving both parts at once, and the throughput based on the
+/// median.
+pub fn bench_table(results: &[DayResult]) -> String {
+    let allocations_header = allocations_header(results);
+    let headers = [
+        "Day",
+        "Part",
+        "Min",
+        "Median",
+        "p95",
+        "Max",
+        "Throughput",
+        &allocations_header,
+    ];
+    let mut rows = Vec::new();
+    for day in results {
+        let parse = day.parse.as_ref().map(|p| ("parse", Some(p)));
+        let parts = day.parts.iter().map(|p| (p.part.name, p.time.as_ref()));
+        let both = day.both.as_ref().map(|b| ("both", Some(b)));
+        for (name, time) in parse.into_iter().chain(parts).chain(both) {
+            let Some(time) = time else {
+                rows.push(vec![
+                    day.day.to_string(),
+                    name.to_string(),
+                    "ERROR".to_string(),
+                ]);
+                continue;
+            };
+            rows.push(vec![
+                day.day.to_string(),
+                name.to_string(),
+                format_duration(time.min()),
+                format_duration(time.median()),
+                format_duration(time.percentile(95)),
+                format_duration(time.max()),
+                time.throughput(day.input_len)
+                    .map_or("-".to_string(), format_throughput),
+                format_allocations(time.allocations()),
+            ]);
+        }
+    }
+
+    let mut table = table(&headers, &rows);
+    let runs = results
+        .iter()
+        .flat_map(|d| d.parts.iter().filter_map(|p| p.time.as_ref()))
+        .map(Measurement::runs)
+        .max();
+    if let Some(runs) = runs {
+        writeln!(
+            table,
+            "\n{} per part, throughput in input bytes per second of the median run",
+            format_runs(runs)
+        )
+        .unwrap();
+    }
+    table
+}
+
+/// Renders a table with every implementation of the parts that have
+/// variants next to each other, with their answer and how much faster they
+/// are than the main implementation. Empty if no part has variants.
+pub fn variants_table(results: &[DayResult]) -> String {
+    let headers = ["Day", "Part", "Answer", "Median", "p95", "Speedup"];
+    let mut rows = Vec::new();
+    for day in results {
+        let with_variants = day
+            .parts
+            .iter()
+            .filter(|p| p.part.variant().is_some())
+            .map(|p| p.part.part)
+            .dedup();
+        for part in with_variants {
+            let implementations = day.parts.iter().filter(|p| p.part.part == part);
+            let main = day
+                .parts
+                .iter()
+                .find(|p| p.part.part == part && p.part.variant().is_none())
+                .and_then(|p| p.time.as_ref());
+            for p in implementations {
+                let time = |f: fn(&Measurement) -> Duration| {
+                    p.time
+                        .as_ref()
+                        .map_or("-".to_string(), |t| format_duration(f(t)))
+                };
+                let speedup = match (main, &p.time) {
+                    (Some(_), Some(_)) if p.part.variant().is_none() => "-".to_string(),
+                    (Some(main), Some(time)) => format_speedup(main.median(), time.median()),
+                    _ => String::new(),
+                };
+                rows.push(vec![
+                    day.day.to_string(),
+                    p.part.name.to_string(),
+                    format_answer(p),
+                    time(Measurement::median),
+                    time(|t| t.percentile(95)),
+                    speedup,
+                ]);
+            }
+        }
+    }
+    if rows.is_empty() {
+        return String::new();
+    }
+    table(&headers, &rows)
+}
+
+/// The answer of a part followed by its status if it is not ok, or only the
+/// status if there is no answer.
+fn format_answer(part: &PartResult) -> String {
+    let status = part.status.as_str().to_uppercase();
+    match (&part.answer, &part.status) {
+        (Some(answer), Status::Ok) => answer.to_string(),
+        (Some(answer), _) => format!("{answer} {status}"),
+        (None, _) => status,
+    }
+}
+
+/// How much faster `time` is than `main`, based on the medians.
+fn format_speedup(main: Duration, time: Duration) -> String {
+    // a median of 0ns only happens with a coarse clock
+    let main = main.as_secs_f64().max(1e-9);
+    let time = time.as_secs_f64().max(1e-9);
+    if time <= main {
+        format!("{:.2}x faster", main / time)
+    } else {
+        format!("{:.2}x slower", time / main)
+    }
+}
+
+/// Renders a table with the median of the baseline and the current run of
+/// every compared part.
+pub fn comparison_table(comparisons: &[Comparison]) -> String {
+    let headers = ["Day", "Part", "Baseline", "Current", "Change"];
+    let mut rows = Vec::new();
+    for c in comparisons {
+        rows.push(vec![
+            c.day.to_string(),
+            c.name.to_string(),
+            c.baseline
+                .map_or("-".to_string(), |b| format_duration(b.median())),
+            format_duration(c.current.median()),
+            c.change.to_string(),
+        ]);
+    }
+
+    table(&headers, &rows)
+}
+
+/// One line with the answer and the verdict of every part, or `ERROR` if
+/// it could not be solved.
+pub fn verdicts(results: &[DayResult]) -> String {
+    let mut lines = String::new();
+    for day in results {
+        for part in &day.parts {
+            let mut name = format!("day{}::{}", day.day, part.part.name);
+            if let Some(input) = &day.input {
+                name += &format!(" [{input}]");
+            }
+            match (&part.answer, &part.verdict) {
+                (Some(answer), Some(verdict)) => writeln!(lines, "{name} = {answer} {verdict}"),
+                _ => writeln!(lines, "{name} ERROR"),
+            }
+            .unwrap();
+        }
+    }
+    lines
+}
+
+/// One line per input of the corpus with the parts that are wrong or could
+/// not be solved, or whether all answers are known to be right.
+pub fn corpus_summary(loaded: &[Loaded], results: &[DayResult]) -> String {
+    let rows = loaded
+        .iter()
+        .zip(results)
+        .map(|(l, day)| {
+            let path = l.source.path(day.day).unwrap_or_default();
+            let failed = day
+                .parts
+                .iter()
+                .filter(|p| {
+                    p.status != Status::Ok || matches!(p.verdict, Some(Verdict::Fail { .. }))
+                })
+                .map(|p| p.part.name)
+                .collect::<Vec<_>>();
+            let unknown = day
+                .parts
+                .iter()
+                .any(|p| p.verdict == Some(Verdict::Unknown));
+            let verdict = match (failed.is_empty(), unknown) {
+                (false, _) => format!("FAIL ({})", failed.join(", ")),
+                (true, true) => "UNKNOWN".to_string(),
+                (true, false) => "PASS".to_string(),
+            };
+            (path.display().to_string(), verdict)
+        })
+        .collect::<Vec<_>>();
+    let width = rows.iter().map(|(path, _)| path.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|(path, verdict)| format!("{path:<width$}  {verdict}"))
+        .join("\n")
+}
+
+/// The wall-clock time of all days one after the other, and with more than
+/// one job if that was measured. Says how many days were left out.
+pub fn wall_clock(time: &WallClock) -> String {
+    let days = match time.measured == time.days {
+        true => "All days".to_string(),
+        false => format!("{} of {} days", time.measured, time.days),
+    };
+    let mut line = format!(
+        "{days} took {} one after the other",
+        format_duration(time.sequential.mean())
+    );
+    if let Some((jobs, parallel)) = &time.parallel {
+        line += &format!(" and {} with {jobs} jobs", format_duration(parallel.mean()));
+    }
+    line + &format!(
+        " (wall-clock, mean of {})",
+        format_runs(time.sequential.runs())
+    )
+}
+
+/// The columns that hold text, they are left aligned and the columns with
+/// numbers right aligned.
+const TEXT_COLUMNS: [&str; 5] = ["Part", "Answer", "Speedup", "Change", "Allocations"];
+
+/// Renders rows below their headers in columns as wide as their widest
+/// cell. Rows may leave out empty cells at the end, and an empty row is a
+/// rule across the table.
+fn table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let headers_row = headers.iter().map(|h| h.to_string()).collect::<Vec<_>>();
+    let rows = iter::once(&headers_row).chain(rows);
+    let mut widths = vec![0; headers.len()];
+    for row in rows.clone() {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for row in rows {
+        if row.is_empty() {
+            let columns = widths.iter().filter(|&&w| w > 0).count();
+            let len = widths.iter().sum::<usize>() + 2 * columns.saturating_sub(1);
+            writeln!(table, "{}", "-".repeat(len)).unwrap();
+            continue;
+        }
+        let line = row
+            .iter()
+            .zip(headers.iter().zip(&widths))
+            .map(|(cell, (header, &w))| {
+                // a part that could not be measured is marked like text
+                if TEXT_COLUMNS.contains(header) || cell == "ERROR" {
+                    format!("{cell:<w$}")
+                } else {
+                    format!("{cell:>w$}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(table, "{}", line.trim_end()).unwrap();
+    }
+    table
+}
+
+/// The header of the allocations column, which is left out if allocations
+/// are not counted.
+fn allocations_header(results: &[DayResult]) -> String {
+    let counted = results
+        .iter()
+        .flat_map(|d| {
+            d.parse
+                .iter()
+                .chain(d.parts.iter().filter_map(|p| p.time.as_ref()))
+        })
+        .any(|t| t.allocations().is_some());
+    if counted {
+        "Allocations".to_string()
+    } else {
+        String::new()
+    }
+}
+
+fn format_allocations(allocations: Option<Allocations>) -> String {
+    allocations.map_or(String::new(), |a| {
+        format!(
+            "{}, {}, peak {}",
+            a.count,
+            format_bytes(a.bytes as f64),
+            format_bytes(a.peak as f64)
+        )
+    })
+}
+
+/// Formats bytes per second with a decimal unit prefix.
+fn format_throughput(bytes_per_sec: f64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec))
+}
+
+/// Formats bytes with a decimal unit prefix.
+fn format_bytes(bytes: f64) -> String {
+    let units = ["B", "kB", "MB", "GB", "TB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < units.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{value:.0} {}", units[unit])
+    } else {
+        format!("{value:.2} {}", units[unit])
+    }
+}
+
+pub fn format_duration(d: Duration) -> String {
+    format!("{d:.2?}")
+}
+
+/// E.g. `1 run` or `10 runs`.
+pub fn format_runs(runs: usize) -> String {
+    match runs {
+        1 => "1 run".to_string(),
+        _ => format!("{runs} runs"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::baseline::Baseline;
+    use crate::input::InputSource;
+    use crate::registry;
+    use crate::runner::{failed_day, run_day, Timer};
+
+    fn results() -> Vec<DayResult> {
+        let timer = Timer {
+            warmup: 0,
+            runs: 1,
+            timeout: None,
+        };
+        vec![
+            run_day(
+                registry::get(1).unwrap(),
+                None,
+                "1\n\n2\n\n3\n",
+                timer,
+                |_| (),
+            ),
+            failed_day(registry::get(3).unwrap(), Some(2), "input missing"),
+            run_day(
+                registry::get(7).unwrap(),
+                None,
+                "$ cd /\n40000001 a\n",
+                timer,
+                |_| (),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_summary_table() {
+        let table = summary_table(&results());
+        let lines = table.lines().collect::<Vec<_>>();
+        // both parts at once of days 1 and 7, day 3 failed
+        assert_eq!(lines.len(), 1 + 6 + 2 + 2 + 2);
+        assert!(lines[0].trim_start().starts_with("Day  Part        Answer"));
+        assert!(lines[1].trim_start().starts_with("1  part1       3 "));
+        assert!(lines[1].contains(" - "));
+        assert!(lines[3].trim_start().starts_with("1  both          "));
+        assert!(lines[4].trim_start().starts_with("3  part2       ERROR"));
+        assert!(!lines[6].contains(" - "));
+        assert!(lines[7].contains(" - "));
+        assert!(lines[8].trim_start().starts_with("7  both "));
+        assert!(lines[10].starts_with("Total"));
+        assert!(lines[12].ends_with("(mean of 1 run per part)"));
+    }
+
+    /// The totals and the runs are left out if nothing was measured.
+    #[test]
+    fn test_summary_table_unsolved() {
+        let timer = Timer {
+            warmup: 0,
+            runs: 1,
+            timeout: None,
+        };
+        let day12 = registry::get(12).unwrap();
+        let results = [run_day(day12, None, "Sabc\nxyzE\n", timer, |_| ())];
+        let table = summary_table(&results);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert!(lines[4].starts_with("Total"));
+        assert!(lines[4].ends_with(" -"), "{table}");
+        assert!(lines[6].starts_with("Parsing took "));
+        assert!(lines[6].ends_with("(mean of 1 run), no part was solved"));
+
+        let results = [failed_day(day12, None, "input missing")];
+        let table = summary_table(&results);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5, "{table}");
+        assert_eq!(
+            lines[4].split_whitespace().collect::<Vec<_>>(),
+            ["Total", "-", "-"]
+        );
+        assert!(!bench_table(&results).contains("runs"));
+    }
+
+    #[test]
+    fn test_json() {
+        let json = render(Format::Json, &results());
+        let records: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let records = records.as_array().unwrap();
+        assert_eq!(records.len(), 6);
+        assert_eq!(records[0]["day"], 1);
+        assert_eq!(records[0]["part"], 1);
+        assert_eq!(records[0]["answer"], 3);
+        assert_eq!(records[0]["status"], "ok");
+        assert_eq!(records[0]["runs"], 1);
+        assert!(records[0]["parse_ns"].is_null());
+        assert_eq!(records[3]["variant"], "simd");
+        assert_eq!(records[3]["status"], "error");
+        assert_eq!(records[3]["message"], "input missing");
+        assert!(records[3]["answer"].is_null());
+        assert!(records[4]["parse_ns"].is_u64());
+        assert!(records[5]["parse_ns"].is_null());
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = render(Format::Csv, &results());
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 7);
+        assert!(lines[1].starts_with("1,1,,3,ok,,,"));
+        assert_eq!(lines[4], "3,2,simd,,error,input missing,,,,,,,,0,,,,,,");
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = render(Format::Markdown, &results());
+        let lines = markdown.lines().collect::<Vec<_>>();
+        assert_eq!(lines.first(), Some(&"```plain"));
+        assert_eq!(lines.last(), Some(&"```"));
+        // 2 parts of days 1 and 7, day 7 has no benchmark of its parse step
+        assert_eq!(lines.len(), 2 + 4);
+        assert!(lines[1].starts_with("test day1::tests::bench_part1 ... bench: "));
+        assert!(lines[3].starts_with("test day7::tests::bench_part1 ... bench: "));
+        assert!(lines[1].contains(" ns/iter (+/- "));
+
+        let mut results = results();
+        results[2].day = 11;
+        let markdown = render(Format::Markdown, &results);
+        let lines = markdown.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2 + 5);
+        assert!(lines[3].starts_with("test day11::tests::bench_parse_monkeys ... bench: "));
+    }
+
+    #[test]
+    fn test_bench_table() {
+        let table = bench_table(&results());
+        let lines = table.lines().collect::<Vec<_>>();
+        // header, 2 parts and both of day 1, 2 of day 3, parse, 2 parts and
+        // both of day 7
+        assert_eq!(lines.len(), 1 + 9 + 2);
+        assert!(lines[0].trim_start().starts_with("Day  Part "));
+        assert!(lines[0].contains("Throughput"));
+        assert!(lines[1].contains("/s"));
+        assert!(lines[3].trim_start().starts_with("1  both "));
+        assert!(lines[5].trim_start().starts_with("3  part2_simd  ERROR"));
+        assert!(lines[6].trim_start().starts_with("7  parse "));
+        assert!(lines[9].trim_start().starts_with("7  both "));
+        assert!(lines[11].starts_with("1 run per part"));
+    }
+
+    #[test]
+    fn test_comparison_table() {
+        let results = results();
+        let baseline = Baseline::from_results(&results[..1]);
+        let table = comparison_table(&baseline.compare(&results, 1000.0));
+        let lines = table.lines().collect::<Vec<_>>();
+        // header, 2 parts of day 1, parse and 2 parts of day 7
+        assert_eq!(lines.len(), 1 + 5);
+        assert!(lines[0].ends_with("Change"));
+        assert!(lines[1].trim_start().starts_with("1  part1 "));
+        assert!(lines[1].ends_with("+0.0%"));
+        assert!(lines[3].trim_start().starts_with("7  parse "));
+        assert!(lines[3].ends_with("NEW"));
+    }
+
+    #[test]
+    fn test_variants_table() {
+        let results = results();
+        assert_eq!(variants_table(&results[..1]), "");
+        let failed = variants_table(&results);
+        assert!(failed.lines().nth(2).unwrap().contains("part2_simd  ERROR"));
+
+        let timer = Timer {
+            warmup: 0,
+            runs: 1,
+            timeout: None,
+        };
+        let day3 = registry::get(3).unwrap();
+        let results = [run_day(day3, None, "abca\nxbcx\nbyyb\n", timer, |_| ())];
+        let table = variants_table(&results);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].trim_start().starts_with("Day  Part        Answer"));
+        assert!(lines[1].trim_start().starts_with("3  part2       2 "));
+        assert!(lines[1].ends_with(" -"));
+        assert!(lines[2].trim_start().starts_with("3  part2_simd  2 "));
+        assert!(lines[2].contains("x faster") || lines[2].contains("x slower"));
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let mut results = results();
+        results[0].parts[0].verdict = Some(Verdict::Pass);
+        results[0].parts[1].verdict = Some(Verdict::Fail {
+            expected: Answer::Integer(5),
+        });
+        results[2].input = Some("alice".to_string());
+        results[2].parts[0].verdict = Some(Verdict::Unknown);
+        let verdicts = verdicts(&results[..3]);
+        let lines = verdicts.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "day1::part1 = 3 PASS");
+        assert_eq!(lines[1], "day1::part2 = 6 FAIL (expected 5)");
+        assert_eq!(lines[2], "day3::part2 ERROR");
+        assert_eq!(lines[3], "day3::part2_simd ERROR");
+        assert_eq!(lines[4], "day7::part1 [alice] = 0 UNKNOWN");
+        // solved, but not verified
+        assert_eq!(lines[5], "day7::part2 [alice] ERROR");
+    }
+
+    #[test]
+    fn test_corpus_summary() {
+        let mut results = results();
+        results[0].parts[0].verdict = Some(Verdict::Pass);
+        results[0].parts[1].verdict = Some(Verdict::Unknown);
+        results[2].parts[0].verdict = Some(Verdict::Fail {
+            expected: Answer::Integer(5),
+        });
+        let loaded = results
+            .iter()
+            .zip(["inputs/corpus/day1/a.txt", "b.txt", "c.txt"])
+            .map(|(day, path)| Loaded {
+                solution: registry::get(day.day).unwrap(),
+                source: InputSource::from_arg(path),
+                name: None,
+                input: Ok(String::new()),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            corpus_summary(&loaded, &results),
+            "inputs/corpus/day1/a.txt  UNKNOWN\n\
+             b.txt                     FAIL (part2, part2_simd)\n\
+             c.txt                     FAIL (part1)"
+        );
+    }
+
+    #[test]
+    fn test_wall_clock() {
+        let time = |ms| Measurement::new(vec![Duration::from_millis(ms); 2]);
+        let mut wall = WallClock {
+            days: 3,
+            measured: 3,
+            sequential: time(3),
+            parallel: None,
+        };
+        assert_eq!(
+            wall_clock(&wall),
+            "All days took 3.00ms one after the other (wall-clock, mean of 2 runs)"
+        );
+        wall.measured = 2;
+        wall.parallel = Some((4, time(2)));
+        assert_eq!(
+            wall_clock(&wall),
+            "2 of 3 days took 3.00ms one after the other and 2.00ms with 4 jobs (wall-clock, mean of 2 runs)"
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let rows = [
+            vec!["1".to_string(), "part1".to_string(), "1.50ms".to_string()],
+            vec!["12".to_string(), "part2".to_string(), "ERROR".to_string()],
+            vec![],
+            vec!["Total".to_string()],
+        ];
+        assert_eq!(
+            table(&["Day", "Part", "Mean", ""], &rows),
+            "  Day  Part     Mean\n    1  part1  1.50ms\n   12  part2  ERROR\n\
+             --------------------\nTotal\n"
+        );
+    }
+
+    #[test]
+    fn test_format_speedup() {
+        let ms = Duration::from_millis;
+        assert_eq!(format_speedup(ms(30), ms(10)), "3.00x faster");
+        assert_eq!(format_speedup(ms(10), ms(15)), "1.50x slower");
+        assert_eq!(format_speedup(ms(10), ms(10)), "1.00x faster");
+    }
+
+    #[test]
+    fn test_format_throughput() {
+        assert_eq!(format_throughput(12.0), "12 B/s");
+        assert_eq!(format_throughput(1_234_567.0), "1.23 MB/s");
+        assert_eq!(format_throughput(5e15), "5000.00 TB/s");
+        assert_eq!(format_bytes(999.0), "999 B");
+    }
+
+    #[test]
+    fn test_format_allocations() {
+        assert_eq!(format_allocations(None), "");
+        let allocations = Allocations {
+            count: 3,
+            bytes: 1500,
+            peak: 1000,
+        };
+        assert_eq!(
+            format_allocations(Some(allocations)),
+            "3, 1.50 kB, peak 1.00 kB"
+        );
+    }
+
+    #[test]
+    fn test_thousands() {
+        assert_eq!(thousands(0), "0");
+        assert_eq!(thousands(999), "999");
+        assert_eq!(thousands(1201), "1,201");
+        assert_eq!(thousands(3321320), "3,321,320");
+    }
+}
@@ -0,0 +1,1150 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::hint::black_box;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Once};
+use std::time::{Duration, Instant};
+
+use itertools::Itertools;
+
+use crate::allocations::{self, Allocations};
+use crate::answer::Answer;
+use crate::answers::Verdict;
+use crate::input::{InputError, InputSource, Inputs};
+use crate::registry::{self, Day, Parsed, PartId};
+
+/// Measures closures with a number of warm-up runs before the measured runs.
+#[derive(Debug, Clone, Copy)]
+pub struct Timer {
+    pub warmup: u32,
+    pub runs: u32,
+    /// A part that takes longer than this for a single run is given up on
+    /// and reported as timed out. Only enforced by [`run_day`].
+    pub timeout: Option<Duration>,
+}
+
+impl Timer {
+    /// Runs `f` `warmup + runs` times and returns the result of the last run.
+    /// The allocations are those of the last run.
+    pub fn measure<T>(&self, mut f: impl FnMut() -> T) -> (T, Measurement) {
+        for _ in 0..self.warmup {
+            drop(f());
+        }
+
+        let mut times = Vec::with_capacity(self.runs as usize);
+        let mut result = None;
+        let mut allocations = None;
+        for _ in 0..self.runs.max(1) {
+            let start = Instant::now();
+            let (r, a) = allocations::count(&mut f);
+            times.push(start.elapsed());
+            // don't measure dropping the previous result
+            result = Some(r);
+            allocations = a;
+        }
+        (
+            result.expect("at least one run is measured"),
+            Measurement { times, allocations },
+        )
+    }
+}
+
+/// The wall-clock times of the measured runs of a closure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    times: Vec<Duration>,
+    /// Only counted with the `count-allocations` feature.
+    allocations: Option<Allocations>,
+}
+
+impl Measurement {
+    #[cfg(test)]
+    pub(crate) fn new(times: Vec<Duration>) -> Self {
+        Self {
+            times,
+            allocations: None,
+        }
+    }
+
+    pub fn runs(&self) -> usize {
+        self.times.len()
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.times.iter().sum::<Duration>() / self.times.len() as u32
+    }
+
+    pub fn min(&self) -> Duration {
+        self.times.iter().copied().min().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.times.iter().copied().max().unwrap_or_default()
+    }
+
+    pub fn allocations(&self) -> Option<Allocations> {
+        self.allocations
+    }
+
+    pub fn median(&self) -> Duration {
+        let sorted = self.sorted();
+        match sorted.len() {
+            0 => Duration::ZERO,
+            n if n % 2 == 0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+            n => sorted[n / 2],
+        }
+    }
+
+    /// The time that `percentile` percent of the runs took at most, using
+    /// the nearest rank.
+    pub fn percentile(&self, percentile: u32) -> Duration {
+        let sorted = self.sorted();
+        let rank = (sorted.len() * percentile as usize).div_ceil(100);
+        sorted
+            .get(rank.saturating_sub(1))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Bytes of input per second, based on the median run.
+    pub fn throughput(&self, bytes: usize) -> Option<f64> {
+        let median = self.median().as_secs_f64();
+        (median > 0.0).then(|| bytes as f64 / median)
+    }
+
+    fn sorted(&self) -> Vec<Duration> {
+        let mut sorted = self.times.clone();
+        sorted.sort_unstable();
+        sorted
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// The part could not be run, e.g. because the input is missing.
+    Error(String),
+    Panic(String),
+    Timeout(String),
+    /// A variant answered differently than the main implementation of its
+    /// part. Its answer and time are kept.
+    Mismatch(String),
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Error(_) => "error",
+            Status::Panic(_) => "panic",
+            Status::Timeout(_) => "timeout",
+            Status::Mismatch(_) => "mismatch",
+        }
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Status::Ok => None,
+            Status::Error(message)
+            | Status::Panic(message)
+            | Status::Timeout(message)
+            | Status::Mismatch(message) => Some(message),
+        }
+    }
+
+    fn panic(day: u8, name: &str, message: String) -> Self {
+        Status::Panic(format!("day {day}: {name} panicked: {message}"))
+    }
+
+    fn timeout(day: u8, name: &str, timeout: Duration) -> Self {
+        Status::Timeout(format!(
+            "day {day}: {name} took longer than {timeout:?}, giving up on it"
+        ))
+    }
+}
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: PartId,
+    pub status: Status,
+    /// Only set if the part could be solved.
+    pub answer: Option<Answer>,
+    pub time: Option<Measurement>,
+    /// Only set when verifying the answers.
+    pub verdict: Option<Verdict>,
+}
+
+#[derive(Debug)]
+pub struct DayResult {
+    pub day: u8,
+    /// The name of the input if a day is run on more than one, like the
+    /// file stem of an input in the corpus.
+    pub input: Option<String>,
+    /// The size of the input in bytes, 0 if it could not be read.
+    pub input_len: usize,
+    /// Only measured for days with a distinct parse step.
+    pub parse: Option<Measurement>,
+    pub parts: Vec<PartResult>,
+    /// Solving both parts at once with [`Parsed::solve_both`], only
+    /// measured if both parts are run and solved.
+    pub both: Option<Measurement>,
+}
+
+impl DayResult {
+    /// The mean time of parsing plus solving the parts, variants are left
+    /// out as they solve a part again.
+    pub fn total(&self) -> Duration {
+        self.parse
+            .as_ref()
+            .map(Measurement::mean)
+            .unwrap_or_default()
+            + self.solve_total()
+    }
+
+    /// The mean time of solving both parts at once if it was measured,
+    /// otherwise of solving the parts one by one without their variants.
+    pub fn solve_total(&self) -> Duration {
+        if let Some(both) = &self.both {
+            return both.mean();
+        }
+        self.parts
+            .iter()
+            .filter(|p| p.part.variant().is_none())
+            .filter_map(|p| p.time.as_ref().map(Measurement::mean))
+            .sum()
+    }
+}
+
+/// Parses the input once and solves the selected parts, calling
+/// `on_part` as soon as a part is solved. Malformed input fails the
+/// affected parts instead of the whole run.
+///
+/// Every part runs in isolation: a panic only fails the part that panicked,
+/// and with a [`Timer::timeout`] the day runs on another thread so that a
+/// part that takes too long can be given up on.
+///
+/// The answer of every variant is checked against the main implementation
+/// of its part, a different answer is a [`Status::Mismatch`]. If both parts
+/// are run, solving them at once is measured as well.
+pub fn run_day(
+    solution: &'static dyn Day,
+    part: Option<u8>,
+    input: &str,
+    timer: Timer,
+    on_part: impl FnMut(&PartResult),
+) -> DayResult {
+    let parts = solution
+        .parts()
+        .into_iter()
+        .filter(|p| part.is_none_or(|part| part == p.part))
+        .collect::<Vec<_>>();
+    let both = [1, 2]
+        .iter()
+        .all(|&part| parts.iter().any(|p| p.part == part));
+    let (parse, parts, both) = match timer.timeout {
+        None => run_parts(solution, input, &parts, both, timer, on_part),
+        Some(timeout) => {
+            run_parts_with_timeout(solution, input, &parts, both, timer, timeout, on_part)
+        }
+    };
+
+    DayResult {
+        day: solution.day(),
+        input: None,
+        input_len: input.len(),
+        parse: parse.filter(|_| solution.has_parse_step()),
+        parts,
+        both,
+    }
+}
+
+/// The time of parsing, the solved parts and the time of solving both
+/// parts at once.
+type Runs = (Option<Measurement>, Vec<PartResult>, Option<Measurement>);
+
+/// Parses the input and solves `parts` on the current thread, and both
+/// parts at once if `both` is set.
+fn run_parts(
+    solution: &dyn Day,
+    input: &str,
+    parts: &[PartId],
+    both: bool,
+    timer: Timer,
+    mut on_part: impl FnMut(&PartResult),
+) -> Runs {
+    let (parsed, parse) = match parse(solution, input, timer, || ()) {
+        Ok(parsed) => parsed,
+        Err(status) => {
+            let results = parts.iter().map(|&p| failed_part(p, status.clone()));
+            return (None, results.inspect(|r| on_part(r)).collect(), None);
+        }
+    };
+    let mut results = Vec::with_capacity(parts.len());
+    for &p in parts {
+        let result = solve(solution.day(), &*parsed, p, timer, || ());
+        push_part(solution.day(), &mut results, result, &mut on_part);
+    }
+    let solved = results.iter().all(|r| r.answer.is_some());
+    let both = (both && solved)
+        .then(|| solve_both(&*parsed, timer, || ()))
+        .flatten();
+    (Some(parse), results, both)
+}
+
+/// Like [`run_parts`], but on other threads that report after every run,
+/// so that a run that takes longer than `timeout` can be given up on. The
+/// thread of such a run is left behind, and a new thread parses the input
+/// again for the remaining parts. Both parts at once are only solved if no
+/// part was given up on.
+fn run_parts_with_timeout(
+    solution: &'static dyn Day,
+    input: &str,
+    parts: &[PartId],
+    both: bool,
+    timer: Timer,
+    timeout: Duration,
+    mut on_part: impl FnMut(&PartResult),
+) -> Runs {
+    enum Event {
+        /// A single run of parsing or of a part is done.
+        Ran,
+        Parsed(Result<Measurement, Status>),
+        Solved(PartResult),
+        SolvedBoth(Option<Measurement>),
+    }
+
+    let input: Arc<str> = Arc::from(input);
+    let mut parse_time = None;
+    let mut both_time = None;
+    let mut results = Vec::new();
+    while results.len() < parts.len() {
+        let remaining = parts[results.len()..].to_vec();
+        // only parsing on the first thread is measured
+        let parse_timer = if results.is_empty() {
+            timer
+        } else {
+            Timer {
+                warmup: 0,
+                runs: 1,
+                ..timer
+            }
+        };
+        let both = both && results.is_empty();
+        let (tx, rx) = mpsc::channel();
+        let worker_input = Arc::clone(&input);
+        std::thread::spawn(move || {
+            let ran = || drop(tx.send(Event::Ran));
+            let parsed = match parse(solution, &worker_input, parse_timer, ran) {
+                Ok((parsed, parse)) => {
+                    let _ = tx.send(Event::Parsed(Ok(parse)));
+                    parsed
+                }
+                Err(status) => return drop(tx.send(Event::Parsed(Err(status)))),
+            };
+            let mut solved = true;
+            for p in remaining {
+                let result = solve(solution.day(), &*parsed, p, timer, ran);
+                solved &= result.answer.is_some();
+                let _ = tx.send(Event::Solved(result));
+            }
+            if both && solved {
+                let time = solve_both(&*parsed, timer, ran);
+                let _ = tx.send(Event::SolvedBoth(time));
+            }
+        });
+
+        let mut parsed = false;
+        loop {
+            match rx.recv_timeout(timeout) {
+                Ok(Event::Ran) => {}
+                Ok(Event::Parsed(Ok(parse))) => {
+                    parsed = true;
+                    parse_time = parse_time.or(Some(parse));
+                }
+                Ok(Event::Parsed(Err(status))) => {
+                    for &p in &parts[results.len()..] {
+                        let result = failed_part(p, status.clone());
+                        push_part(solution.day(), &mut results, result, &mut on_part);
+                    }
+                }
+                Ok(Event::Solved(result)) => {
+                    push_part(solution.day(), &mut results, result, &mut on_part);
+                }
+                Ok(Event::SolvedBoth(time)) => both_time = time,
+                Err(RecvTimeoutError::Timeout) if !parsed => {
+                    let status = Status::timeout(solution.day(), "parsing", timeout);
+                    for &p in &parts[results.len()..] {
+                        let result = failed_part(p, status.clone());
+                        push_part(solution.day(), &mut results, result, &mut on_part);
+                    }
+                    break;
+                }
+                // only solving both parts at once is left
+                Err(RecvTimeoutError::Timeout) if results.len() == parts.len() => break,
+                Err(RecvTimeoutError::Timeout) => {
+                    let p = parts[results.len()];
+                    let result = failed_part(p, Status::timeout(solution.day(), p.name, timeout));
+                    push_part(solution.day(), &mut results, result, &mut on_part);
+                    break;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    }
+    (parse_time, results, both_time)
+}
+
+thread_local! {
+    /// Whether the current thread runs a closure in [`quietly`].
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` and catches a panic, returning its message instead of letting
+/// the panic hook print it and a backtrace. Panics of other threads are
+/// printed as usual.
+pub fn quietly<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                default(info)
+            }
+        }));
+    });
+    let quiet = QUIET.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.set(quiet);
+    result.map_err(panic_message)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Parses the input, calling `ran` after every run.
+fn parse<'a>(
+    solution: &dyn Day,
+    input: &'a str,
+    timer: Timer,
+    ran: impl Fn(),
+) -> Result<(Box<dyn Parsed + 'a>, Measurement), Status> {
+    let measured = quietly(|| {
+        timer.measure(|| {
+            let parsed = solution.parse(input);
+            ran();
+            parsed
+        })
+    });
+    match measured {
+        Ok((Ok(parsed), time)) => Ok((parsed, time)),
+        Ok((Err(e), _)) => Err(Status::Error(e.to_string())),
+        Err(message) => Err(Status::panic(solution.day(), "parsing", message)),
+    }
+}
+
+/// Solves a single part, calling `ran` after every run.
+fn solve(day: u8, parsed: &dyn Parsed, p: PartId, timer: Timer, ran: impl Fn()) -> PartResult {
+    let measured = quietly(|| {
+        timer.measure(|| {
+            let answer = parsed.solve(p);
+            ran();
+            answer
+        })
+    });
+    match measured {
+        Ok((Ok(answer), time)) => PartResult {
+            part: p,
+            status: Status::Ok,
+            answer: Some(answer),
+            time: Some(time),
+            verdict: None,
+        },
+        Ok((Err(e), _)) => failed_part(p, Status::Error(e.to_string())),
+        Err(message) => failed_part(p, Status::panic(day, p.name, message)),
+    }
+}
+
+/// Solves both parts at once, calling `ran` after every run. `None` if
+/// that fails, the parts report why on their own.
+fn solve_both(parsed: &dyn Parsed, timer: Timer, ran: impl Fn()) -> Option<Measurement> {
+    let measured = quietly(|| {
+        timer.measure(|| {
+            let answers = parsed.solve_both();
+            ran();
+            answers
+        })
+    });
+    match measured {
+        Ok((Ok(_), time)) => Some(time),
+        _ => None,
+    }
+}
+
+/// Cross-checks a solved part with the parts solved before it and adds it
+/// to them. The main implementation of a part comes before its variants.
+fn push_part(
+    day: u8,
+    results: &mut Vec<PartResult>,
+    mut result: PartResult,
+    on_part: &mut impl FnMut(&PartResult),
+) {
+    let main = results
+        .iter()
+        .find(|r| r.part.part == result.part.part && r.part.variant().is_none());
+    if let (Some(main), Some(answer), Some(_)) = (main, &result.answer, result.part.variant()) {
+        if let Some(expected) = main.answer.as_ref().filter(|&a| a != answer) {
+            result.status = Status::Mismatch(format!(
+                "day {day}: {} answered {answer}, but {} answered {expected}",
+                result.part.name, main.part.name
+            ));
+        }
+    }
+    on_part(&result);
+    results.push(result);
+}
+
+fn failed_part(part: PartId, status: Status) -> PartResult {
+    PartResult {
+        part,
+        status,
+        answer: None,
+        time: None,
+        verdict: None,
+    }
+}
+
+/// The result of a day that could not be run at all, e.g. because the
+/// input is missing.
+pub fn failed_day(solution: &dyn Day, part: Option<u8>, message: &str) -> DayResult {
+    let parts = solution
+        .parts()
+        .into_iter()
+        .filter(|p| part.is_none_or(|part| part == p.part))
+        .map(|p| failed_part(p, Status::Error(message.to_string())))
+        .collect();
+
+    DayResult {
+        day: solution.day(),
+        input: None,
+        input_len: 0,
+        parse: None,
+        parts,
+        both: None,
+    }
+}
+
+/// Calls `f` with every item on up to `jobs` threads, the results are in
+/// the order of the items.
+pub fn run_parallel<T: Sync, R: Send>(
+    jobs: usize,
+    items: &[T],
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let mut results = items.iter().map(|_| None).collect::<Vec<_>>();
+    std::thread::scope(|s| {
+        let workers = (0..jobs.clamp(1, items.len().max(1)))
+            .map(|_| {
+                s.spawn(|| {
+                    // every worker takes the next item until all are taken
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(i) else {
+                            return done;
+                        };
+                        done.push((i, f(item)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for worker in workers {
+            let done = worker
+                .join()
+                .unwrap_or_else(|e| std::panic::resume_unwind(e));
+            for (i, result) in done {
+                results[i] = Some(result);
+            }
+        }
+    });
+    results
+        .into_iter()
+        .map(|r| r.expect("every item is run"))
+        .collect()
+}
+
+/// Parses and solves the selected parts of every day once, with up to
+/// `jobs` days at the same time. Only used to measure the wall-clock time of
+/// all days together, so errors are ignored.
+pub fn solve_all(days: &[(&dyn Day, &str)], part: Option<u8>, jobs: usize) {
+    run_parallel(jobs, days, |(solution, input)| {
+        let Ok(parsed) = solution.parse(input) else {
+            return;
+        };
+        // variants are left out, they are not part of solving a day
+        let main = solution.parts().into_iter().find(|p| Some(p.part) == part);
+        match main {
+            Some(main) => drop(black_box(parsed.solve(main))),
+            None => drop(black_box(parsed.solve_both())),
+        }
+    });
+}
+
+/// A selected day with its input.
+pub struct Loaded {
+    pub solution: &'static dyn Day,
+    pub source: InputSource,
+    /// Only set for inputs of the corpus, which are named in the results.
+    pub name: Option<String>,
+    pub input: Result<String, InputError>,
+}
+
+/// The loaded inputs of the selected days, with the days that were skipped
+/// and the ones that could not be loaded.
+#[derive(Default)]
+pub struct Loading {
+    pub loaded: Vec<Loaded>,
+    pub skipped: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// Loads the inputs of the given days, days that are not solved yet are
+/// errors.
+pub fn load(days: impl IntoIterator<Item = u8>, inputs: &Inputs) -> Loading {
+    let mut loading = Loading::default();
+    for day in days {
+        let Some(solution) = registry::get(day) else {
+            loading.errors.push(format!("day {day} is not solved yet"));
+            continue;
+        };
+        let source = inputs.source(day);
+        loading.loaded.push(Loaded {
+            solution,
+            input: source.read(day),
+            name: None,
+            source,
+        });
+    }
+    loading
+}
+
+/// Loads every input in the corpus of the given days. Days without a
+/// corpus are skipped, but at least one selected day needs one.
+pub fn load_corpus(days: impl IntoIterator<Item = u8>, inputs: &Inputs) -> Loading {
+    let mut loading = Loading::default();
+    for day in days {
+        let Some(solution) = registry::get(day) else {
+            loading.errors.push(format!("day {day} is not solved yet"));
+            continue;
+        };
+        match inputs.corpus(day) {
+            Ok(sources) if sources.is_empty() => loading.skipped.push(format!(
+                "day {day}: no inputs in {}, skipping it",
+                inputs.corpus_dir(day).display()
+            )),
+            Ok(sources) => loading
+                .loaded
+                .extend(sources.into_iter().map(|source| Loaded {
+                    solution,
+                    input: source.read(day),
+                    name: Some(source.name()),
+                    source,
+                })),
+            Err(e) => loading.errors.push(e.to_string()),
+        }
+    }
+    if loading.loaded.is_empty() && loading.errors.is_empty() {
+        loading
+            .errors
+            .push(format!("the corpus in {} is empty", inputs.dir().display()));
+    }
+    loading
+}
+
+/// Solves the loaded days with up to `jobs` days at the same time.
+///
+/// `on_part` is called in day and part order, as soon as a part is solved
+/// if the days are solved one after the other, or once all days are solved.
+pub fn run_loaded(
+    loaded: &[Loaded],
+    part: Option<u8>,
+    timer: Timer,
+    jobs: usize,
+    mut on_part: impl FnMut(u8, &PartResult),
+) -> Vec<DayResult> {
+    let run = |l: &Loaded, on_part: &mut dyn FnMut(&PartResult)| {
+        let mut result = match &l.input {
+            Ok(input) => run_day(l.solution, part, input, timer, on_part),
+            Err(e) => failed_day(l.solution, part, &e.to_string()),
+        };
+        result.input.clone_from(&l.name);
+        result
+    };
+    if jobs > 1 {
+        let results = run_parallel(jobs, loaded, |l| run(l, &mut |_| ()));
+        for result in &results {
+            result.parts.iter().for_each(|p| on_part(result.day, p));
+        }
+        results
+    } else {
+        loaded
+            .iter()
+            .map(|l| run(l, &mut |p| on_part(l.solution.day(), p)))
+            .collect()
+    }
+}
+
+/// The messages of the parts that could not be solved, with the name of
+/// their input if it has one.
+pub fn errors(results: &[DayResult]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for result in results {
+        // both parts usually fail with the same parse error
+        let messages = result
+            .parts
+            .iter()
+            .filter_map(|p| p.status.message())
+            .unique();
+        errors.extend(messages.map(|message| match &result.input {
+            Some(input) => format!("{message} (input {input})"),
+            None => message.to_string(),
+        }));
+    }
+    errors
+}
+
+/// The wall-clock time of solving the loaded days, see [`wall_clock`].
+#[derive(Debug)]
+pub struct WallClock {
+    /// The number of loaded days.
+    pub days: usize,
+    /// The number of days that were measured, the others have a part that
+    /// could not be solved.
+    pub measured: usize,
+    pub sequential: Measurement,
+    /// With the number of jobs, only measured with more than one job.
+    pub parallel: Option<(usize, Measurement)>,
+}
+
+/// The wall-clock time of solving all loaded days once one after the
+/// other, and with up to `jobs` days at the same time if `jobs` is more
+/// than one. Days with a part that could not be solved are left out, they
+/// might panic or not finish at all, and nothing is measured without days.
+pub fn wall_clock(
+    loaded: &[Loaded],
+    results: &[DayResult],
+    part: Option<u8>,
+    timer: Timer,
+    jobs: usize,
+) -> Option<WallClock> {
+    let days = loaded
+        .iter()
+        .zip(results)
+        .filter(|(_, r)| r.parts.iter().all(|p| p.status == Status::Ok))
+        .filter_map(|(l, _)| Some((l.solution, l.input.as_deref().ok()?)))
+        .collect::<Vec<_>>();
+    if days.is_empty() {
+        return None;
+    }
+    let (_, sequential) = timer.measure(|| solve_all(&days, part, 1));
+    let parallel = (jobs > 1).then(|| (jobs, timer.measure(|| solve_all(&days, part, jobs)).1));
+    Some(WallClock {
+        days: loaded.len(),
+        measured: days.len(),
+        sequential,
+        parallel,
+    })
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ParseError;
+    use crate::registry::{self, Registered};
+    use crate::solution::{Solution, Variant};
+
+    #[test]
+    fn test_timer() {
+        let timer = Timer {
+            warmup: 2,
+            runs: 3,
+            timeout: None,
+        };
+        let mut calls = 0;
+        let (result, time) = timer.measure(|| {
+            calls += 1;
+            calls
+        });
+        assert_eq!(result, 5);
+        assert_eq!(time.runs(), 3);
+        assert!(time.min() <= time.mean() && time.mean() <= time.max());
+        assert_eq!(time.allocations().is_some(), allocations::enabled());
+
+        let (_, time) = timer.measure(|| vec![0_u8; 100]);
+        if let Some(allocations) = time.allocations() {
+            assert_eq!(allocations.count, 1);
+            assert_eq!(allocations.bytes, 100);
+        }
+    }
+
+    #[test]
+    fn test_statistics() {
+        let time = Measurement::new((1..=20).rev().map(Duration::from_millis).collect());
+        assert_eq!(time.min(), Duration::from_millis(1));
+        assert_eq!(time.median(), Duration::from_micros(10_500));
+        assert_eq!(time.percentile(95), Duration::from_millis(19));
+        assert_eq!(time.percentile(100), time.max());
+        assert_eq!(time.throughput(21).map(f64::round), Some(2000.0));
+
+        let time = Measurement::new(vec![Duration::from_secs(3)]);
+        assert_eq!(time.median(), Duration::from_secs(3));
+        assert_eq!(time.percentile(95), Duration::from_secs(3));
+        assert_eq!(Measurement::new(vec![Duration::ZERO]).throughput(10), None);
+    }
+
+    #[test]
+    fn test_run_day() {
+        let timer = Timer {
+            warmup: 0,
+            runs: 1,
+            timeout: None,
+        };
+        let day7 = registry::get(7).unwrap();
+        let input = "$ cd /\n$ ls\n100 a\n";
+        let mut seen = Vec::new();
+        let result = run_day(day7, Some(1), input, timer, |p| seen.push(p.part));
+        assert_eq!(result.day, 7);
+        assert_eq!(result.input_len, input.len());
+        assert!(result.parse.is_some());
+        assert_eq!(result.parts.len(), 1);
+        assert_eq!(result.parts[0].status, Status::Ok);
+        assert_eq!(result.parts[0].answer, Some(Answer::Integer(100)));
+        assert_eq!(seen, vec![result.parts[0].part]);
+
+        let day1 = registry::get(1).unwrap();
+        let result = run_day(day1, None, "1\n\n2\n\n3\n", timer, |_| ());
+        assert!(result.parse.is_none());
+        assert_eq!(result.parts.len(), 2);
+
+        let result = failed_day(day1, Some(2), "no input");
+        assert_eq!(result.parts.len(), 1);
+        assert_eq!(result.parts[0].status.message(), Some("no input"));
+        assert!(result.parts[0].answer.is_none());
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir().join(format!("aoc-load-{}", std::process::id()));
+        let inputs = Inputs::new(&dir);
+        std::fs::create_dir_all(inputs.corpus_dir(1)).unwrap();
+        std::fs::write(dir.join("day1.txt"), "1\n\n2\n\n3\n").unwrap();
+        std::fs::write(inputs.corpus_dir(1).join("alice.txt"), "1\nx\n").unwrap();
+        let loading = load([1, 2, 25], &inputs);
+        let corpus = load_corpus([1, 2], &inputs);
+        let empty = load_corpus([2], &inputs);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loading.errors, ["day 25 is not solved yet"]);
+        assert_eq!(loading.loaded.len(), 2);
+        assert!(loading.loaded[1].input.is_err());
+        let timer = Timer {
+            warmup: 0,
+            runs: 1,
+            timeout: None,
+        };
+        let mut seen = Vec::new();
+        let results = run_loaded(&loading.loaded, None, timer, 2, |day, p| {
+            seen.push((day, p.part.name))
+        });
+        assert_eq!(
+            seen,
+            [(1, "part1"), (1, "part2"), (2, "part1"), (2, "part2")]
+        );
+        // both parts of day 2 fail with the same message
+        let messages = errors(&results);
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("day2.txt"), "{messages:?}");
+
+        // day 2 has no input
+        let time = wall_clock(&loading.loaded, &results, None, timer, 1).unwrap();
+        assert_eq!((time.measured, time.days), (1, 2));
+        assert_eq!(time.sequential.runs(), 1);
+        assert!(time.parallel.is_none());
+        let time = wall_clock(&loading.loaded, &results, Some(1), timer, 2).unwrap();
+        assert_eq!(
+            time.parallel.map(|(jobs, p)| (jobs, p.runs())),
+            Some((2, 1))
+        );
+        assert!(wall_clock(&loading.loaded[1..], &results[1..], None, timer, 1).is_none());
+
+        assert!(corpus.errors.is_empty());
+        assert_eq!(corpus.skipped.len(), 1);
+        assert!(corpus.skipped[0].starts_with("day 2: no inputs in "));
+        assert_eq!(corpus.loaded.len(), 1);
+        assert_eq!(corpus.loaded[0].name.as_deref(), Some("alice"));
+        let results = run_loaded(&corpus.loaded, Some(1), timer, 1, |_, _| ());
+        assert_eq!(results[0].input.as_deref(), Some("alice"));
+        assert!(errors(&results)[0].ends_with(" (input alice)"));
+
+        assert!(empty.loaded.is_empty());
+        assert_eq!(
+            empty.errors,
+            [format!("the corpus in {} is empty", dir.display())]
+        );
+    }
+
+    #[test]
+    fn test_total() {
+        let day3 = registry::get(3).unwrap();
+        let input = "abca\nxbcx\nbyyb\n";
+        for timeout in [None, Some(Duration::from_secs(10))] {
+            let timer = Timer {
+                warmup: 0,
+                runs: 3,
+                timeout,
+            };
+            let result = run_day(day3, None, input, timer, |_| ());
+            let both = result.both.as_ref().unwrap();
+            assert_eq!(both.runs(), 3);
+            assert_eq!(result.solve_total(), both.mean());
+            let parse = result.parse.as_ref().unwrap().mean();
+            assert_eq!(result.total(), parse + both.mean());
+
+            // without both parts, the variants are still left out
+            let result = run_day(day3, Some(2), input, timer, |_| ());
+            assert!(result.both.is_none());
+            assert_eq!(result.parts[1].part.variant(), Some("simd"));
+            let part2 = result.parts[0].time.as_ref().unwrap().mean();
+            assert_eq!(result.solve_total(), part2);
+        }
+    }
+
+    /// Sleeps for 100ms per byte of input in part 1, and panics if the input
+    /// says so.
+    struct Sleepy;
+
+    impl Solution for Sleepy {
+        const DAY: u8 = 25;
+
+        type Parsed<'a> = &'a str;
+
+        fn parse(input: &str) -> Result<&str, ParseError> {
+            if input == "panic while parsing" {
+                panic!("cannot parse");
+            }
+            Ok(input)
+        }
+
+        fn part1(input: &&str) -> Result<Answer, ParseError> {
+            if *input == "panic" {
+                panic!("boom");
+            }
+            std::thread::sleep(Duration::from_millis(100) * input.len() as u32);
+            Ok(Answer::Integer(1))
+        }
+
+        fn part2(_: &&str) -> Result<Answer, ParseError> {
+            Ok(Answer::Integer(2))
+        }
+    }
+
+    static SLEEPY: Registered<Sleepy> = Registered::new();
+
+    /// Has two variants of part 1, one of them is wrong for inputs of an odd
+    /// length.
+    struct Variants;
+
+    impl Solution for Variants {
+        const DAY: u8 = 24;
+
+        type Parsed<'a> = &'a str;
+
+        fn parse(input: &str) -> Result<&str, ParseError> {
+            Ok(input)
+        }
+
+        fn part1(input: &&str) -> Result<Answer, ParseError> {
+            Ok(Answer::Integer(input.len() as i64))
+        }
+
+        fn part2(_: &&str) -> Result<Answer, ParseError> {
+            Ok(Answer::Integer(0))
+        }
+
+        fn variants() -> Vec<Variant<Self>> {
+            vec![
+                Variant {
+                    part: 1,
+                    name: "part1_same",
+                    run: Self::part1,
+                },
+                Variant {
+                    part: 1,
+                    name: "part1_even",
+                    run: |input| Ok(Answer::Integer(input.len() as i64 / 2 * 2)),
+                },
+            ]
+        }
+    }
+
+    static VARIANTS: Registered<Variants> = Registered::new();
+
+    #[test]
+    fn test_cross_check() {
+        for timeout in [None, Some(Duration::from_secs(10))] {
+            let timer = Timer {
+                warmup: 0,
+                runs: 1,
+                timeout,
+            };
+            let result = run_day(&VARIANTS, Some(1), "ab", timer, |_| ());
+            assert!(result.parts.iter().all(|p| p.status == Status::Ok));
+
+            let mut seen = Vec::new();
+            let result = run_day(&VARIANTS, None, "abc", timer, |p| {
+                seen.push((p.part.name, p.status.as_str()))
+            });
+            assert_eq!(
+                seen,
+                [
+                    ("part1", "ok"),
+                    ("part1_same", "ok"),
+                    ("part1_even", "mismatch"),
+                    ("part2", "ok")
+                ]
+            );
+            assert_eq!(
+                result.parts[2].status.message(),
+                Some("day 24: part1_even answered 2, but part1 answered 3")
+            );
+            assert_eq!(result.parts[2].answer, Some(Answer::Integer(2)));
+            assert!(result.parts[2].time.is_some());
+        }
+    }
+
+    #[test]
+    fn test_panic() {
+        for timeout in [None, Some(Duration::from_secs(10))] {
+            let timer = Timer {
+                warmup: 1,
+                runs: 2,
+                timeout,
+            };
+            let result = run_day(&SLEEPY, None, "panic", timer, |_| ());
+            assert_eq!(
+                result.parts[0].status,
+                Status::Panic("day 25: part1 panicked: boom".to_string())
+            );
+            assert_eq!(result.parts[1].answer, Some(Answer::Integer(2)));
+
+            let result = run_day(&SLEEPY, None, "panic while parsing", timer, |_| ());
+            assert_eq!(result.parts.len(), 2);
+            assert_eq!(
+                result.parts[1].status.message(),
+                Some("day 25: parsing panicked: cannot parse")
+            );
+        }
+    }
+
+    #[test]
+    fn test_quietly() {
+        assert_eq!(quietly(|| 1), Ok(1));
+        assert_eq!(
+            quietly(|| panic!("boom {}", 1)),
+            Err::<(), _>("boom 1".to_string())
+        );
+        // a nested call doesn't make the outer one print its panic
+        let nested = quietly(|| {
+            assert_eq!(quietly(|| 2), Ok(2));
+            panic!("boom")
+        });
+        assert_eq!(nested, Err::<(), _>("boom".to_string()));
+        assert!(!QUIET.get());
+    }
+
+    #[test]
+    fn test_timeout() {
+        let timer = Timer {
+            warmup: 0,
+            runs: 1,
+            timeout: Some(Duration::from_millis(50)),
+        };
+        let mut seen = Vec::new();
+        let result = run_day(&SLEEPY, None, "sleep", timer, |p| seen.push(p.part.name));
+        assert_eq!(result.parts[0].status.as_str(), "timeout");
+        assert_eq!(
+            result.parts[0].status.message(),
+            Some("day 25: part1 took longer than 50ms, giving up on it")
+        );
+        assert_eq!(result.parts[1].answer, Some(Answer::Integer(2)));
+        assert_eq!(seen, ["part1", "part2"]);
+
+        let result = run_day(&SLEEPY, None, "", timer, |_| ());
+        assert_eq!(result.parts[0].answer, Some(Answer::Integer(1)));
+    }
+
+    #[test]
+    fn test_run_parallel() {
+        let items = (0..50).collect::<Vec<u64>>();
+        let squares = items.iter().map(|i| i * i).collect::<Vec<_>>();
+        for jobs in [1, 4, 100] {
+            assert_eq!(run_parallel(jobs, &items, |i| i * i), squares);
+        }
+        assert!(run_parallel(4, &[] as &[u64], |i| *i).is_empty());
+
+        let timer = Timer {
+            warmup: 0,
+            runs: 1,
+            timeout: None,
+        };
+        let inputs = ["1\n\n2\n\n3\n", "A Y\n", "$ cd /\n40000001 a\n"];
+        let days = [1, 2, 7].map(|d| registry::get(d).unwrap());
+        let items = days.iter().zip(inputs).collect::<Vec<_>>();
+        let results = run_parallel(3, &items, |(day, input)| {
+            run_day(**day, None, input, timer, |_| ())
+        });
+        let answers = results
+            .iter()
+            .map(|r| (r.day, r.parts[0].answer.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            answers,
+            [
+                (1, Some(Answer::Integer(3))),
+                (2, Some(Answer::Integer(8))),
+                (7, Some(Answer::Integer(0)))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let timer = Timer {
+            warmup: 0,
+            runs: 1,
+            timeout: None,
+        };
+        // day 7 fails while parsing, so every part fails
+        let day7 = registry::get(7).unwrap();
+        let result = run_day(day7, None, "$ cd /\n$ cd ..\n$ cd ..\n", timer, |_| ());
+        assert_eq!(result.parts.len(), 2);
+        assert!(result.parse.is_none());
+        assert_eq!(
+            result.parts[0].status.message(),
+            Some("day 7: line 3, column 6: expected a directory below the root, found `.`")
+        );
+
+        // day 1 parses while solving, and part 2 needs three elves
+        let day1 = registry::get(1).unwrap();
+        let result = run_day(day1, None, "1\n\n2\n", timer, |_| ());
+        assert_eq!(result.parts[0].answer, Some(Answer::Integer(2)));
+        assert_eq!(result.parts[1].status.as_str(), "error");
+        assert!(result.parts[1].time.is_none());
+    }
+}
@@ -0,0 +1,342 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_SRC_DIR: &str = "src";
+
+/// The module of a new day, with `{{day}}` and `{{title}}` to fill in.
+const TEMPLATE: &str = include_str!("../templates/day.rs.in");
+/// rustfmt's default, which the registered imports are wrapped at.
+const MAX_WIDTH: usize = 100;
+
+/// The module of a new day, with placeholder parts that aren't solved yet
+/// and a test and benchmark for each of them.
+pub fn module(day: u8, title: Option<&str>) -> String {
+    TEMPLATE.replace("{{day}}", &day.to_string()).replace(
+        "{{title}}",
+        &title.map(|t| format!(": {t}")).unwrap_or_default(),
+    )
+}
+
+/// Adds the module of a day to `lib.rs`, in the order rustfmt keeps the
+/// modules in.
+pub fn declare(lib: &str, day: u8) -> Result<String, String> {
+    let module = format!("day{day}");
+    let declaration = format!("pub mod {module};");
+    let mut lines = lib.lines().collect::<Vec<_>>();
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, line.strip_prefix("pub mod ")?.strip_suffix(';')?)))
+        .collect::<Vec<_>>();
+    if modules.iter().any(|&(_, m)| m == module) {
+        return Err(format!("day {day} is declared already"));
+    }
+    let Some(&(last, _)) = modules.last() else {
+        return Err("expected `pub mod` declarations".to_string());
+    };
+    let at = modules
+        .iter()
+        .find(|&&(_, m)| m > module.as_str())
+        .map_or(last + 1, |&(i, _)| i);
+    lines.insert(at, &declaration);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds a day to the imports and to the days of `registry.rs`.
+pub fn register(registry: &str, day: u8) -> Result<String, String> {
+    let module = format!("day{day}");
+    let (head, rest) = registry
+        .split_once("use crate::{")
+        .ok_or("expected `use crate::{`")?;
+    let (imports, tail) = rest.split_once("};").ok_or("expected `};`")?;
+    let mut imports = imports
+        .split(',')
+        .map(str::trim)
+        .filter(|import| !import.is_empty())
+        .collect::<Vec<_>>();
+    if imports.contains(&module.as_str()) {
+        return Err(format!("day {day} is registered already"));
+    }
+    imports.push(&module);
+    imports.sort_unstable();
+
+    let entry = format!("    &Registered::<{module}::Day{day}>::new(),");
+    let mut lines = tail.lines().collect::<Vec<_>>();
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let day = line
+                .trim()
+                .strip_prefix("&Registered::<day")?
+                .split_once("::")?
+                .0
+                .parse::<u8>()
+                .ok()?;
+            Some((i, day))
+        })
+        .collect::<Vec<_>>();
+    let Some(&(last, _)) = days.last() else {
+        return Err("expected `&Registered::<dayN::DayN>::new()` entries".to_string());
+    };
+    let at = days
+        .iter()
+        .find(|&&(_, d)| d > day)
+        .map_or(last + 1, |&(i, _)| i);
+    lines.insert(at, &entry);
+
+    Ok(format!(
+        "{head}{}{}\n",
+        use_crate(&imports),
+        lines.join("\n")
+    ))
+}
+
+/// `use crate::{...};` the way rustfmt formats it.
+fn use_crate(imports: &[&str]) -> String {
+    let single = format!("use crate::{{{}}};", imports.join(", "));
+    if single.len() <= MAX_WIDTH {
+        return single;
+    }
+    let mut lines: Vec<String> = Vec::new();
+    for import in imports {
+        match lines.last_mut() {
+            // rustfmt keeps the lines of a list below the maximum width
+            Some(line) if line.len() + import.len() + 2 < MAX_WIDTH => {
+                *line += &format!(" {import},")
+            }
+            _ => lines.push(format!("    {import},")),
+        }
+    }
+    format!("use crate::{{\n{}\n}};", lines.join("\n"))
+}
+
+/// Creates the module of a new day in `src_dir`, registers it and creates
+/// an empty placeholder for its input in `inputs_dir` unless the input
+/// exists already. Nothing is written if the day cannot be added. Returns
+/// the files that were written.
+pub fn create(
+    day: u8,
+    title: Option<&str>,
+    src_dir: &Path,
+    inputs_dir: &Path,
+) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let path = src_dir.join(format!("day{day}.rs"));
+    if path.exists() {
+        return Err(ScaffoldError::Exists { path });
+    }
+    let edit = |file: &str, edit: fn(&str, u8) -> Result<String, String>| {
+        let path = src_dir.join(file);
+        let content = std::fs::read_to_string(&path).map_err(|source| ScaffoldError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let content = edit(&content, day).map_err(|message| ScaffoldError::Unexpected {
+            path: path.clone(),
+            message,
+        })?;
+        Ok((path, content))
+    };
+    let mut files = vec![
+        (path, module(day, title)),
+        edit("lib.rs", declare)?,
+        edit("registry.rs", register)?,
+    ];
+    let input = inputs_dir.join(format!("day{day}.txt"));
+    if !input.exists() {
+        files.push((input, String::new()));
+    }
+
+    for (path, content) in &files {
+        let io_error = |source| ScaffoldError::Io {
+            path: path.clone(),
+            source,
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        std::fs::write(path, content).map_err(io_error)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The module of the day exists already.
+    Exists { path: PathBuf },
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// A file that the day is registered in doesn't look as expected.
+    Unexpected { path: PathBuf, message: String },
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::Exists { path } => write!(f, "{} exists already", path.display()),
+            ScaffoldError::Io { path, source } => {
+                write!(f, "cannot access {}: {source}", path.display())
+            }
+            ScaffoldError::Unexpected { path, message } => {
+                write!(
+                    f,
+                    "cannot register the day in {}: {message}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScaffoldError::Io { source, .. } => Some(source),
+            ScaffoldError::Exists { .. } | ScaffoldError::Unexpected { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::tests::temp_dir;
+    use crate::registry;
+
+    const LIB: &str = "pub mod answer;
+pub mod day1;
+pub mod day13;
+pub mod day14;
+pub mod day2;
+pub mod parse;
+";
+
+    const REGISTRY: &str = "use crate::solution::Solution;
+use crate::{
+    day1, day10, day11, day12, day13, day14, day2, day3, day4, day5, day6, day7, day8, day9,
+};
+
+static DAYS: &[&dyn Day] = &[
+    &Registered::<day1::Day1>::new(),
+    &Registered::<day2::Day2>::new(),
+    &Registered::<day14::Day14>::new(),
+];
+";
+
+    #[test]
+    fn test_module() {
+        let module = module(15, Some("Beacon Exclusion Zone"));
+        assert!(module.starts_with("//! Day 15: Beacon Exclusion Zone\n"));
+        assert!(module.contains("pub struct Day15;"));
+        assert!(module.contains("const DAY: u8 = 15;"));
+        assert!(module.contains("test_input(15)"));
+        assert!(!module.contains("{{"));
+        assert!(self::module(3, None).starts_with("//! Day 3\n"));
+    }
+
+    #[test]
+    fn test_declare() {
+        let lib = declare(LIB, 15).unwrap();
+        assert!(lib.contains("pub mod day14;\npub mod day15;\npub mod day2;\n"));
+        assert_eq!(lib.len(), LIB.len() + "pub mod day15;\n".len());
+        assert!(declare(&lib, 15).is_err());
+        assert!(declare(LIB, 0)
+            .unwrap()
+            .contains("pub mod answer;\npub mod day0;\npub mod day1;"));
+        assert!(declare("fn main() {}\n", 15).is_err());
+    }
+
+    #[test]
+    fn test_register() {
+        let registry = register(REGISTRY, 15).unwrap();
+        assert_eq!(
+            registry,
+            "use crate::solution::Solution;
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day2, day3, day4, day5, day6, day7, day8, day9,
+};
+
+static DAYS: &[&dyn Day] = &[
+    &Registered::<day1::Day1>::new(),
+    &Registered::<day2::Day2>::new(),
+    &Registered::<day14::Day14>::new(),
+    &Registered::<day15::Day15>::new(),
+];
+"
+        );
+        assert!(register(&registry, 15).is_err());
+        assert!(register(&registry, 16).unwrap().contains(
+            "use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day2, day3, day4, day5, day6, day7,
+    day8, day9,
+};"
+        ));
+
+        let registry = "use crate::{day1, day3};\n\nstatic DAYS: &[&dyn Day] = &[
+    &Registered::<day1::Day1>::new(),
+    &Registered::<day3::Day3>::new(),
+];
+";
+        assert_eq!(
+            register(registry, 2).unwrap(),
+            "use crate::{day1, day2, day3};\n\nstatic DAYS: &[&dyn Day] = &[
+    &Registered::<day1::Day1>::new(),
+    &Registered::<day2::Day2>::new(),
+    &Registered::<day3::Day3>::new(),
+];
+"
+        );
+        assert!(register("use crate::{day1};\n", 2).is_err());
+    }
+
+    /// Adds the next day to copies of the real `lib.rs` and `registry.rs`.
+    #[test]
+    fn test_create() {
+        let lib = include_str!("lib.rs");
+        let registry = include_str!("registry.rs");
+        let Some(day) = (1..=25).find(|&d| registry::get(d).is_none()) else {
+            return;
+        };
+        let dir = temp_dir("scaffold");
+        let (src, inputs) = (dir.join("src"), dir.join("inputs"));
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("lib.rs"), lib).unwrap();
+        std::fs::write(src.join("registry.rs"), registry).unwrap();
+
+        let written = create(day, None, &src, &inputs).unwrap();
+        assert_eq!(
+            written,
+            [
+                src.join(format!("day{day}.rs")),
+                src.join("lib.rs"),
+                src.join("registry.rs"),
+                inputs.join(format!("day{day}.txt"))
+            ]
+        );
+        let registered = std::fs::read_to_string(src.join("registry.rs")).unwrap();
+        assert!(registered.contains(&format!("&Registered::<day{day}::Day{day}>::new(),")));
+        assert_eq!(
+            std::fs::read_to_string(&written[3]).unwrap(),
+            "",
+            "the input is an empty placeholder"
+        );
+        let e = create(day, None, &src, &inputs).unwrap_err();
+        assert!(matches!(e, ScaffoldError::Exists { .. }));
+
+        // nothing is written if a file cannot be changed, an existing input is kept
+        let next = day + 1;
+        std::fs::write(src.join("registry.rs"), "").unwrap();
+        std::fs::write(inputs.join(format!("day{next}.txt")), "1\n").unwrap();
+        let e = create(next, None, &src, &inputs).unwrap_err();
+        assert!(e.to_string().starts_with("cannot register the day in"));
+        assert!(!src.join(format!("day{next}.rs")).exists());
+        assert!(!std::fs::read_to_string(src.join("lib.rs"))
+            .unwrap()
+            .contains(&format!("day{next};")));
+        std::fs::write(src.join("registry.rs"), registry).unwrap();
+        assert_eq!(create(next, None, &src, &inputs).unwrap().len(), 3);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,47 @@
+use crate::answer::Answer;
+use crate::parse::ParseError;
+
+/// The solution of a single day.
+///
+/// The input is parsed once with [`Solution::parse`] and then handed
+/// to both parts, or to [`Solution::solve`] to solve both at once. Days
+/// that don't have a distinct parse step just use the input itself as
+/// their parsed representation, and parse it while solving the parts
+/// instead. Either way, malformed input is reported as a [`ParseError`].
+pub trait Solution {
+    const DAY: u8;
+
+    type Parsed<'a>;
+
+    /// Whether [`Solution::parse`] does actual work, so that it's worth
+    /// measuring on its own.
+    const HAS_PARSE_STEP: bool = false;
+
+    /// The name of the benchmark of the parse step, like
+    /// `bench_parse_monkeys`, if the day has one.
+    const PARSE_BENCH: Option<&'static str> = None;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, ParseError>;
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, ParseError>;
+
+    /// Both parts, overridden by days that can share work between them.
+    fn solve(parsed: &Self::Parsed<'_>) -> Result<(Answer, Answer), ParseError> {
+        Ok((Self::part1(parsed)?, Self::part2(parsed)?))
+    }
+
+    /// Alternative implementations of either part, like `day3::part2_simd`.
+    fn variants() -> Vec<Variant<Self>> {
+        Vec::new()
+    }
+}
+
+/// An alternative implementation of one of the parts of a [`Solution`].
+pub struct Variant<S: Solution + ?Sized> {
+    pub part: u8,
+    /// The name of the implementation, e.g. `part2_simd`.
+    pub name: &'static str,
+    pub run: fn(&S::Parsed<'_>) -> Result<Answer, ParseError>,
+}
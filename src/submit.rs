@@ -0,0 +1,369 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use crate::answer::Answer;
+use crate::answers::{Answers, Verdict};
+use crate::fetch::{Client, FetchError, YEAR};
+
+/// What the website said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    /// Wrong, without saying whether it is too high or too low.
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, with the time that is left to
+    /// wait if the page says so.
+    RateLimited(Option<Duration>),
+    /// The part is solved already, so the answer was not checked.
+    AlreadySolved,
+    /// The page says none of the above, with its text.
+    Unknown(String),
+}
+
+impl Outcome {
+    /// Reads the outcome from the page the website answers a submission
+    /// with.
+    pub fn parse(page: &str) -> Self {
+        let text = text(article(page));
+        if text.contains("That's the right answer") {
+            Outcome::Correct
+        } else if text.contains("That's not the right answer") {
+            if text.contains("too high") {
+                Outcome::TooHigh
+            } else if text.contains("too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Outcome::RateLimited(wait_time(&text))
+        } else if text.contains("Did you already complete it?") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown(text)
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::RateLimited(Some(wait)) => {
+                write!(f, "submitted too recently, try again in {wait:?}")
+            }
+            Outcome::RateLimited(None) => write!(f, "submitted too recently, try again later"),
+            Outcome::AlreadySolved => write!(f, "the part is solved already"),
+            Outcome::Unknown(text) => write!(f, "unknown response: {text}"),
+        }
+    }
+}
+
+/// Submits the answer of a part.
+pub fn submit(client: &Client, day: u8, part: u8, answer: &Answer) -> Result<Outcome, FetchError> {
+    let page = client.post_form(
+        day,
+        &format!("/{YEAR}/day/{day}/answer"),
+        &[
+            ("level", &part.to_string()),
+            ("answer", &answer.to_string()),
+        ],
+    )?;
+    Ok(Outcome::parse(&page))
+}
+
+/// Why an answer is not submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// A grid with letters that cannot be read.
+    Undecodable,
+    KnownRight,
+    /// A different answer is known to be right.
+    KnownAnswer(Answer),
+    /// The answer was rejected before, or is out of the bounds that the
+    /// rejected answers give.
+    KnownWrong,
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::Undecodable => write!(f, "the answer cannot be decoded"),
+            Refusal::KnownRight => write!(f, "the answer is known to be right"),
+            Refusal::KnownAnswer(expected) => write!(f, "the answer is known to be {expected}"),
+            Refusal::KnownWrong => write!(f, "the answer is known to be wrong"),
+        }
+    }
+}
+
+/// Whether an answer should not be submitted, because the answers file
+/// already says whether it is right.
+pub fn refusal(
+    answers: &Answers,
+    day: u8,
+    input: &str,
+    part: u8,
+    answer: &Answer,
+) -> Option<Refusal> {
+    if !answer.is_decoded() {
+        return Some(Refusal::Undecodable);
+    }
+    match answers.check(day, input, part, answer) {
+        Verdict::Pass => Some(Refusal::KnownRight),
+        Verdict::Fail { expected } => Some(Refusal::KnownAnswer(expected)),
+        Verdict::Unknown if answers.is_rejected(day, input, part, answer) => {
+            Some(Refusal::KnownWrong)
+        }
+        Verdict::Unknown => None,
+    }
+}
+
+/// Remembers what the website said about a submitted answer, returns
+/// whether it said anything about it.
+pub fn record(
+    answers: &mut Answers,
+    day: u8,
+    input: &str,
+    part: u8,
+    answer: Answer,
+    outcome: &Outcome,
+) -> bool {
+    match outcome {
+        Outcome::Correct => answers.set(day, input, part, answer),
+        Outcome::Wrong => answers.reject(day, input, part, answer, None),
+        Outcome::TooHigh => answers.reject(day, input, part, answer, Some(Ordering::Greater)),
+        Outcome::TooLow => answers.reject(day, input, part, answer, Some(Ordering::Less)),
+        Outcome::RateLimited(_) | Outcome::AlreadySolved | Outcome::Unknown(_) => return false,
+    }
+    true
+}
+
+/// The content of the `<article>` of a page, which holds the message, or
+/// the whole page if it has none.
+fn article(page: &str) -> &str {
+    page.split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article)
+}
+
+/// The text of some HTML, without tags and with collapsed whitespace.
+fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads e.g. `You have 1m 5s left to wait.`
+fn wait_time(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (time, _) = rest.split_once(" left to wait")?;
+    let mut seconds = 0;
+    for unit in time.split_whitespace() {
+        let (n, factor) = [("h", 3600), ("m", 60), ("s", 1)]
+            .into_iter()
+            .find_map(|(suffix, factor)| Some((unit.strip_suffix(suffix)?, factor)))?;
+        seconds += n.parse::<u64>().ok()? * factor;
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::tests::{serve, temp_dir};
+    use crate::fetch::Config;
+
+    const CORRECT: &str = r#"<main>
+<article><p>That's the right answer!  You are <span class="day-success">one gold star</span> closer to collecting enough star fruit. <a href="/2022/day/1#part2">[Continue to Part Two]</a></p></article>
+</main>"#;
+
+    const TOO_HIGH: &str = r#"<main>
+<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href="/2022/about">about page</a>.  Please wait one minute before trying again. <a href="/2022/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+    const RATE_LIMITED: &str = r#"<main>
+<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href="/2022/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Outcome::parse(CORRECT), Outcome::Correct);
+        assert_eq!(Outcome::parse(TOO_HIGH), Outcome::TooHigh);
+        assert_eq!(
+            Outcome::parse(&TOO_HIGH.replace("too high", "too low")),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            Outcome::parse(&TOO_HIGH.replace("; your answer is too high", ".")),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            Outcome::parse(RATE_LIMITED),
+            Outcome::RateLimited(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            Outcome::parse("<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>"),
+            Outcome::AlreadySolved
+        );
+        assert_eq!(
+            Outcome::parse("<html><body>\n  Something <b>else</b>\n</body></html>"),
+            Outcome::Unknown("Something else".to_string())
+        );
+    }
+
+    #[test]
+    fn test_refusal() {
+        let mut answers = Answers::default();
+        answers.set(1, "default", 1, Answer::Integer(24000));
+        let refusal =
+            |answers: &Answers, part, answer| refusal(answers, 1, "default", part, &answer);
+        assert_eq!(
+            refusal(&answers, 1, Answer::Integer(24000)),
+            Some(Refusal::KnownRight)
+        );
+        assert_eq!(
+            refusal(&answers, 1, Answer::Integer(7)),
+            Some(Refusal::KnownAnswer(Answer::Integer(24000)))
+        );
+        assert_eq!(refusal(&answers, 2, Answer::Integer(7)), None);
+        assert_eq!(
+            refusal(&answers, 2, Answer::grid("#...\n".repeat(6))),
+            Some(Refusal::Undecodable)
+        );
+        // only grids can be undecodable
+        assert_eq!(refusal(&answers, 2, Answer::from("AB?D")), None);
+    }
+
+    #[test]
+    fn test_resubmit_wrong() {
+        let mut answers = Answers::default();
+        let wrong = Answer::Integer(500);
+        assert_eq!(refusal(&answers, 11, "default", 1, &wrong), None);
+        assert!(record(
+            &mut answers,
+            11,
+            "default",
+            1,
+            wrong.clone(),
+            &Outcome::Wrong
+        ));
+        assert_eq!(
+            refusal(&answers, 11, "default", 1, &wrong),
+            Some(Refusal::KnownWrong)
+        );
+        let grid = Answer::from("ABCDEFGH");
+        record(
+            &mut answers,
+            10,
+            "default",
+            2,
+            grid.clone(),
+            &Outcome::Wrong,
+        );
+        assert_eq!(
+            refusal(&answers, 10, "default", 2, &grid),
+            Some(Refusal::KnownWrong)
+        );
+        // the other inputs and parts are unaffected
+        assert_eq!(refusal(&answers, 11, "alice", 1, &wrong), None);
+        assert_eq!(refusal(&answers, 11, "default", 2, &wrong), None);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut answers = Answers::default();
+        let mut submitted = |answer, outcome| {
+            assert!(record(
+                &mut answers,
+                11,
+                "default",
+                1,
+                Answer::Integer(answer),
+                &outcome
+            ));
+        };
+        submitted(1000, Outcome::TooHigh);
+        submitted(2000, Outcome::TooHigh);
+        submitted(20, Outcome::TooLow);
+        submitted(10, Outcome::TooLow);
+
+        let refused = |answer| refusal(&answers, 11, "default", 1, &Answer::Integer(answer));
+        for answer in [1000, 1001, 5000, 20, 19, -3] {
+            assert_eq!(refused(answer), Some(Refusal::KnownWrong), "{answer}");
+        }
+        for answer in [21, 500, 999] {
+            assert_eq!(refused(answer), None, "{answer}");
+        }
+    }
+
+    #[test]
+    fn test_record() {
+        let mut answers = Answers::default();
+        let answer = Answer::Integer(45000);
+        assert!(!record(
+            &mut answers,
+            1,
+            "default",
+            2,
+            answer.clone(),
+            &Outcome::AlreadySolved
+        ));
+        assert_eq!(answers, Answers::default());
+        assert!(record(
+            &mut answers,
+            1,
+            "default",
+            2,
+            answer.clone(),
+            &Outcome::Correct
+        ));
+        assert_eq!(answers.get(1, "default", 2), Some(&answer));
+    }
+
+    #[test]
+    fn test_wait_time() {
+        assert_eq!(
+            wait_time("You have 37s left to wait."),
+            Some(Duration::from_secs(37))
+        );
+        assert_eq!(wait_time("You have a while left to wait."), None);
+        assert_eq!(wait_time("Please wait."), None);
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, requests) = serve(vec![(200, CORRECT)]);
+        let dir = temp_dir("submit");
+        let config = Config {
+            session: Some("abc".to_string()),
+            base_url: Some(base_url),
+            request_interval: Some(0.0),
+        };
+        let client = Client::new(&config, &dir);
+        let answer = Answer::from("EGJBGCFK");
+        assert_eq!(submit(&client, 10, 2, &answer).unwrap(), Outcome::Correct);
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2022/day/10/answer HTTP/1.1\r\n"));
+        assert!(request
+            .to_lowercase()
+            .contains("\r\ncontent-type: application/x-www-form-urlencoded"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=EGJBGCFK"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}